@@ -7,6 +7,9 @@ use toml::from_str;
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub default_font: String,
+    /// Seconds between automatic saves of persistent state. Disabled when absent.
+    #[serde(default)]
+    pub autosave_interval: Option<u64>,
 }
 
 pub fn read_settings<P: AsRef<Path>>(path: P) -> Result<Config> {
@@ -3,13 +3,13 @@ mod macros;
 use anyhow::{Context, Result};
 use iced::{
     Color, Theme,
-    theme::{Base, Palette},
+    theme::{Base, Palette, palette},
 };
 use macros::register_themes;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fs, path::Path};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UserTheme {
     pub name: String,
     pub background: Option<String>,
@@ -34,6 +34,21 @@ impl From<UserTheme> for Theme {
     }
 }
 
+impl From<&Theme> for UserTheme {
+    fn from(theme: &Theme) -> Self {
+        let palette = theme.palette();
+        Self {
+            name: theme.name().to_owned(),
+            background: Some(color_to_hex(palette.background)),
+            text: Some(color_to_hex(palette.text)),
+            primary: Some(color_to_hex(palette.primary)),
+            success: Some(color_to_hex(palette.success)),
+            danger: Some(color_to_hex(palette.danger)),
+            warning: Some(color_to_hex(palette.warning)),
+        }
+    }
+}
+
 pub fn hex_to_color(hex: &str) -> Color {
     let hex = hex.trim_start_matches('#');
     let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(0);
@@ -42,6 +57,58 @@ pub fn hex_to_color(hex: &str) -> Color {
     Color::from_rgb(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
 }
 
+pub fn color_to_hex(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
+/// Writes the palette of `theme` to a TOML file readable by [`import_palette`].
+pub fn export_palette<P: AsRef<Path>>(theme: &Theme, path: P) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create theme directory")?;
+    }
+
+    let content =
+        toml::to_string_pretty(&UserTheme::from(theme)).context("Failed to serialize theme")?;
+    fs::write(path, content).context("Failed to write theme file")?;
+    Ok(())
+}
+
+/// Reads a TOML palette file into a named custom theme.
+pub fn import_palette<P: AsRef<Path>>(path: P) -> Result<Theme> {
+    let content = fs::read_to_string(path).context("Failed to read theme file")?;
+    let theme: UserTheme = toml::from_str(&content).context("Failed to parse theme file")?;
+
+    let colors = [
+        ("background", &theme.background),
+        ("text", &theme.text),
+        ("primary", &theme.primary),
+        ("success", &theme.success),
+        ("danger", &theme.danger),
+        ("warning", &theme.warning),
+    ];
+    for (field, color) in colors {
+        if let Some(color) = color
+            && !is_hex_color(color)
+        {
+            anyhow::bail!("Invalid {} color \"{}\", expected #RRGGBB", field, color);
+        }
+    }
+
+    Ok(theme.into())
+}
+
+fn is_hex_color(hex: &str) -> bool {
+    let hex = hex.trim_start_matches('#');
+    hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Returns `true` if the theme's base background color is perceived as dark.
+pub fn is_dark(theme: &Theme) -> bool {
+    palette::is_dark(theme.extended_palette().background.base.color)
+}
+
 register_themes!(
     RosePine => Palette {
         background: Color::from_rgb(0.1, 0.09, 0.14),
@@ -90,10 +157,15 @@ pub fn read_user_themes<P: AsRef<Path>>(path: P) -> Result<Vec<iced::Theme>> {
             if entry.file_type().is_ok_and(|ft| ft.is_file())
                 && entry.file_name().to_string_lossy().to_lowercase().ends_with(".toml")
             {
-                let path = entry.path();
-                let content = fs::read_to_string(&path).ok()?;
-                let theme: UserTheme = toml::from_str(&content).ok()?;
-                Some(theme.into())
+                import_palette(entry.path())
+                    .inspect_err(|e| {
+                        tracing::warn!(
+                            "Skipping malformed theme file \"{}\": {:#}",
+                            entry.path().display(),
+                            e
+                        )
+                    })
+                    .ok()
             } else {
                 None
             }
@@ -102,18 +174,103 @@ pub fn read_user_themes<P: AsRef<Path>>(path: P) -> Result<Vec<iced::Theme>> {
 }
 
 pub fn load_available_themes<P: AsRef<Path>>(path: P) -> HashMap<String, iced::Theme> {
-    tracing::info!("Loading registered themes");
-    let registered_themes = registered_themes();
-
     tracing::info!("Loading user themes");
     let user_themes = read_user_themes(path)
         .inspect_err(|e| tracing::error!("Failed to read user themes: {}", e))
         .unwrap_or_default();
 
+    merge_themes(user_themes)
+}
+
+/// Combines registered, built-in and user themes into a lookup keyed by theme name.
+///
+/// A user theme replaces a registered or built-in theme of the same name, so an exported theme
+/// can be edited in place.
+pub fn merge_themes(user_themes: Vec<iced::Theme>) -> HashMap<String, iced::Theme> {
+    tracing::info!("Loading registered themes");
+    let registered_themes = registered_themes();
+
     registered_themes
         .iter()
-        .chain(user_themes.iter())
         .chain(default_themes().iter())
+        .chain(user_themes.iter())
         .map(|t| (t.name().to_owned(), t.clone()))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_theme_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "{}-{}-{}.toml",
+            env!("WORKSPACE_NAME"),
+            std::process::id(),
+            name
+        ))
+    }
+
+    #[test]
+    fn exported_builtin_themes_round_trip() {
+        for theme in [Theme::TokyoNight, Theme::Dracula, Theme::Light] {
+            let path = temp_theme_path(&theme.to_string().replace(' ', "-"));
+
+            export_palette(&theme, &path).unwrap();
+            let imported = import_palette(&path).unwrap();
+            fs::remove_file(&path).unwrap();
+
+            assert_eq!(imported.to_string(), theme.to_string());
+            assert_eq!(imported.palette(), theme.palette());
+        }
+    }
+
+    #[test]
+    fn malformed_colors_are_rejected() {
+        let path = temp_theme_path("malformed");
+        fs::write(&path, "name = \"Broken\"\nbackground = \"#FFF\"\n").unwrap();
+
+        let result = import_palette(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_user_themes_skips_malformed_files() {
+        let dir = std::env::temp_dir().join(format!(
+            "{}-{}-user-themes",
+            env!("WORKSPACE_NAME"),
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let valid = Theme::custom("Valid", Theme::Nord.palette());
+        export_palette(&valid, dir.join("valid.toml")).unwrap();
+        fs::write(dir.join("broken.toml"), "name = \"Broken\"\nbackground = \"#FFF\"\n").unwrap();
+
+        let themes = read_user_themes(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<_> = themes.unwrap().iter().map(Theme::to_string).collect();
+        assert_eq!(names, ["Valid"]);
+    }
+
+    #[test]
+    fn is_dark_on_builtin_themes() {
+        assert!(!is_dark(&Theme::Light));
+        assert!(!is_dark(&Theme::SolarizedLight));
+        assert!(is_dark(&Theme::Dark));
+        assert!(is_dark(&Theme::Dracula));
+        assert!(is_dark(&Theme::TokyoNight));
+    }
+
+    #[test]
+    fn user_themes_take_precedence() {
+        let palette = Theme::Light.palette();
+        let user_theme = Theme::custom(Theme::TokyoNight.to_string(), palette);
+
+        let themes = merge_themes(vec![user_theme]);
+
+        assert_eq!(themes[&Theme::TokyoNight.to_string()].palette(), palette);
+    }
+}
@@ -8,6 +8,9 @@ pub struct CmdArgs {
     pub verbosity: Option<LevelFilter>,
     #[clap(long)]
     pub log_to_file: bool,
+    /// Do not read or write persistent state
+    #[clap(long)]
+    pub no_persist: bool,
 }
 
 pub fn parse() -> CmdArgs {
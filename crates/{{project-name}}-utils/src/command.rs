@@ -1,7 +1,38 @@
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone)]
+#[cfg(target_os = "windows")]
+const FILE_MANAGER_COMMAND: &str = "explorer";
+#[cfg(target_os = "macos")]
+const FILE_MANAGER_COMMAND: &str = "open";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const FILE_MANAGER_COMMAND: &str = "xdg-open";
+
+/// Captured result of a finished command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// Exit code of the process, `None` if it was terminated by a signal.
+    pub code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandOutput {
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+
+    /// Human-readable exit status, e.g. `exit code 1`.
+    pub fn exit_status(&self) -> String {
+        match self.code {
+            Some(code) => format!("exit code {code}"),
+            None => "terminated by signal".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Command {
     pub program: String,
     pub args: Vec<String>,
@@ -13,6 +44,11 @@ impl Command {
         Self { program: program.into(), args: vec![], cwd: None }
     }
 
+    /// Command that opens `path` in the system file manager.
+    pub fn open_in_file_manager(path: impl Into<PathBuf>) -> Self {
+        Self::new(FILE_MANAGER_COMMAND).arg(path.into().to_string_lossy())
+    }
+
     pub fn arg(mut self, arg: impl Into<String>) -> Self {
         self.args.push(arg.into());
         self
@@ -29,16 +65,45 @@ impl Command {
     }
 
     pub fn run(&self) -> Result<()> {
+        let output = self.output()?;
+        if !output.success() {
+            return Err(anyhow::anyhow!("Command failed ({}): {}", output.exit_status(), self));
+        }
+        Ok(())
+    }
+
+    /// Runs the command to completion and captures its exit code, stdout and stderr.
+    ///
+    /// A non-zero exit code is not an error here, inspect [`CommandOutput::code`] instead.
+    pub fn output(&self) -> Result<CommandOutput> {
         let mut cmd = std::process::Command::new(&self.program);
         cmd.args(&self.args);
         if let Some(cwd) = &self.cwd {
             cmd.current_dir(cwd);
         }
         let output = cmd.output().context("Failed to execute command")?;
-        if !output.status.success() {
-            return Err(anyhow::anyhow!("Command failed: {}", self));
-        }
-        Ok(())
+        Ok(CommandOutput {
+            code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// Opens `path` in the system file manager.
+///
+/// Fails if `path` does not exist. Explorer on Windows exits with a non-zero code even when it
+/// succeeds, so its exit status is ignored there.
+pub fn open_path(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Err(anyhow::anyhow!("Path does not exist: {}", path.display()));
+    }
+
+    let cmd = Command::open_in_file_manager(path);
+    if cfg!(target_os = "windows") {
+        cmd.output().map(|_| ())
+    } else {
+        cmd.run()
     }
 }
 
@@ -0,0 +1,165 @@
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::Cell,
+    fs,
+    panic::{self, PanicHookInfo, UnwindSafe},
+    path::{Path, PathBuf},
+    sync::Once,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+static INSTALL_HOOK: Once = Once::new();
+
+thread_local! {
+    static RECOVERING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs a panic hook that logs the panic through `tracing` and writes a crash report.
+///
+/// The previously installed hook still runs afterwards, so the usual console message is kept.
+/// Panics caught by [`catch_recoverable`] are only logged at debug level, without a report.
+/// Only the first call has an effect; subsequent calls are ignored.
+///
+/// # Arguments
+/// * `crash_dir` - Directory crash reports are written to. Created on demand.
+pub fn install_panic_hook<P: AsRef<Path>>(crash_dir: P) {
+    let crash_dir = crash_dir.as_ref().to_path_buf();
+    INSTALL_HOOK.call_once(move || {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if report_panic(&crash_dir, &panic_message(info)) {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+/// Runs `f`, catching a panic the caller is able to recover from.
+///
+/// Works like [`panic::catch_unwind`], but the hook installed by [`install_panic_hook`] does not
+/// treat the panic as a crash.
+pub fn catch_recoverable<R>(f: impl FnOnce() -> R + UnwindSafe) -> std::thread::Result<R> {
+    let previous = RECOVERING.replace(true);
+    let result = panic::catch_unwind(f);
+    RECOVERING.set(previous);
+    result
+}
+
+/// Returns the message a panic was raised with, if it was a string.
+pub fn panic_payload(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
+
+/// Logs a panic and writes its crash report, unless it is caught by [`catch_recoverable`].
+///
+/// Returns whether the panic was treated as a crash.
+fn report_panic(crash_dir: &Path, message: &str) -> bool {
+    if RECOVERING.get() {
+        tracing::debug!("Recovering from panic: {message}");
+        return false;
+    }
+
+    let backtrace = Backtrace::force_capture();
+    tracing::error!("{message}\n{backtrace}");
+
+    match write_crash_report(crash_dir, message, &backtrace) {
+        Ok(path) => tracing::error!("Crash report written to \"{}\"", path.display()),
+        Err(err) => tracing::error!("Failed to write crash report: {err}"),
+    }
+    true
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    let payload = panic_payload(info.payload());
+
+    match info.location() {
+        Some(location) => format!("Panicked at {location}: {payload}"),
+        None => format!("Panicked: {payload}"),
+    }
+}
+
+fn write_crash_report(dir: &Path, message: &str, backtrace: &Backtrace) -> Result<PathBuf> {
+    fs::create_dir_all(dir).context("Failed to create crash report directory")?;
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let path = dir.join(format!("{}-crash-{}.log", env!("WORKSPACE_NAME"), timestamp));
+    let report = format!(
+        "{} {}\n\n{}\n\n{}\n",
+        env!("WORKSPACE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        message,
+        backtrace
+    );
+
+    fs::write(&path, report).context("Failed to write crash report")?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_payload_reads_string_payloads() {
+        let literal: Box<dyn Any + Send> = Box::new("static message");
+        let owned: Box<dyn Any + Send> = Box::new(String::from("owned message"));
+        let other: Box<dyn Any + Send> = Box::new(42);
+
+        assert_eq!(panic_payload(literal.as_ref()), "static message");
+        assert_eq!(panic_payload(owned.as_ref()), "owned message");
+        assert_eq!(panic_payload(other.as_ref()), "Box<dyn Any>");
+    }
+
+    fn temp_crash_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "{}-{}-{}",
+            env!("WORKSPACE_NAME"),
+            std::process::id(),
+            name
+        ))
+    }
+
+    // The global hook is never installed here, it would outlive the test and catch every
+    // other panic in this binary.
+    #[test]
+    fn catch_recoverable_flags_the_panic_as_recovering() {
+        let result = catch_recoverable(|| {
+            assert!(RECOVERING.get());
+            panic!("feature failed")
+        });
+
+        let payload = result.expect_err("panic is caught");
+        assert_eq!(panic_payload(payload.as_ref()), "feature failed");
+        assert!(!RECOVERING.get());
+    }
+
+    #[test]
+    fn recovering_panics_do_not_write_crash_reports() {
+        let crash_dir = temp_crash_dir("recovering");
+
+        let reported = catch_recoverable(|| report_panic(&crash_dir, "feature failed")).unwrap();
+
+        assert!(!reported);
+        assert!(!crash_dir.exists());
+    }
+
+    #[test]
+    fn crashes_write_a_report() {
+        let crash_dir = temp_crash_dir("crash");
+
+        assert!(report_panic(&crash_dir, "Panicked: app failed"));
+
+        let reports: Vec<_> =
+            fs::read_dir(&crash_dir).unwrap().map(|e| e.unwrap().path()).collect();
+        assert_eq!(reports.len(), 1);
+        assert!(fs::read_to_string(&reports[0]).unwrap().contains("Panicked: app failed"));
+        fs::remove_dir_all(&crash_dir).unwrap();
+    }
+}
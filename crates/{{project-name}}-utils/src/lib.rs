@@ -1,5 +1,8 @@
 pub mod cli;
 pub mod command;
+pub mod crash;
 pub mod io;
 pub mod locale;
 pub mod logging;
+pub mod size;
+pub mod time;
@@ -31,8 +31,11 @@ impl Locale {
     }
 }
 
+/// Locale used when the system locale is unknown or not available.
+pub const DEFAULT_LOCALE: &str = "en-US";
+
 pub fn get_system_locale() -> String {
-    sys_locale::get_locale().unwrap_or("en-US".to_owned())
+    sys_locale::get_locale().unwrap_or(DEFAULT_LOCALE.to_owned())
 }
 
 pub fn read_available_locales<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Locale>> {
@@ -1,18 +1,135 @@
 use std::{
-    fs::File,
-    io::Write,
-    path::Path,
-    sync::{Arc, Mutex, OnceLock},
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock, RwLock},
 };
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use tracing::level_filters::LevelFilter;
-use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    EnvFilter, Registry, fmt, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
+
+use crate::size::format_bytes;
+
+static CURRENT_LOG_LEVEL: RwLock<LevelFilter> = RwLock::new(LevelFilter::INFO);
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static LOG_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Size-based rotation settings for the log file.
+#[derive(Debug, Clone, Copy)]
+pub struct LogRotation {
+    /// Size in bytes after which the log file is rolled over.
+    pub max_size: u64,
+    /// Number of rolled files (`name.1`, `name.2`, ...) to keep.
+    pub max_backups: usize,
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        Self { max_size: 10 * 1024 * 1024, max_backups: 3 }
+    }
+}
+
+/// Append-only log file that rolls itself over according to [`LogRotation`].
+struct LogFile {
+    path: PathBuf,
+    file: Option<File>,
+    size: u64,
+    rotation: Option<LogRotation>,
+}
+
+impl LogFile {
+    fn open(path: &Path, rotation: Option<LogRotation>) -> io::Result<Self> {
+        let mut log_file = Self { path: path.to_path_buf(), file: None, size: 0, rotation };
+        log_file.reopen()?;
+        if log_file.exceeds_limit(0) {
+            log_file.rotate_or_disable();
+        }
+        Ok(log_file)
+    }
+
+    fn reopen(&mut self) -> io::Result<()> {
+        let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = file.metadata()?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+
+    fn exceeds_limit(&self, incoming: usize) -> bool {
+        self.rotation.is_some_and(|rotation| {
+            self.size > 0 && self.size + incoming as u64 > rotation.max_size
+        })
+    }
+
+    fn backup_path(&self, index: usize) -> PathBuf {
+        let mut path = OsString::from(self.path.as_os_str());
+        path.push(format!(".{index}"));
+        PathBuf::from(path)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        let Some(rotation) = self.rotation else {
+            return Ok(());
+        };
 
-static CURRENT_LOG_LEVEL: OnceLock<LevelFilter> = OnceLock::new();
+        // The handle must be closed before renaming, Windows refuses to move open files.
+        self.file = None;
+        let shifted = self.shift_backups(rotation);
+
+        // Reopen even if shifting failed, so logging carries on in the current file.
+        self.reopen()?;
+        shifted
+    }
+
+    /// Rotates, or turns rotation off if that fails so it is not retried on every write.
+    fn rotate_or_disable(&mut self) {
+        if let Err(err) = self.rotate() {
+            // Logging through `tracing` from inside the log writer would recurse.
+            eprintln!("Failed to rotate \"{}\", rotation disabled: {err}", self.path.display());
+            self.rotation = None;
+        }
+    }
+
+    fn shift_backups(&self, rotation: LogRotation) -> io::Result<()> {
+        if rotation.max_backups == 0 {
+            return fs::remove_file(&self.path);
+        }
+
+        for index in (1..rotation.max_backups).rev() {
+            let from = self.backup_path(index);
+            if from.exists() {
+                fs::rename(&from, self.backup_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.backup_path(1))
+    }
+}
+
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.exceeds_limit(buf.len()) {
+            self.rotate_or_disable();
+        }
+        if self.file.is_none() {
+            self.reopen()?;
+        }
+
+        let file = self.file.as_mut().ok_or_else(|| io::Error::other("Log file is closed"))?;
+        let written = file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.as_mut().map_or(Ok(()), |file| file.flush())
+    }
+}
 
-/// Wrapper to make Arc<Mutex<File>> work with tracing's MakeWriter trait
-struct MutexWriter(Arc<Mutex<File>>);
+/// Wrapper to make Arc<Mutex<LogFile>> work with tracing's MakeWriter trait
+struct MutexWriter(Arc<Mutex<LogFile>>);
 
 impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for MutexWriter {
     type Writer = MutexWriterGuard;
@@ -22,7 +139,7 @@ impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for MutexWriter {
     }
 }
 
-struct MutexWriterGuard(Arc<Mutex<File>>);
+struct MutexWriterGuard(Arc<Mutex<LogFile>>);
 
 impl Write for MutexWriterGuard {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
@@ -35,7 +152,38 @@ impl Write for MutexWriterGuard {
 }
 
 pub fn get_log_level() -> LevelFilter {
-    CURRENT_LOG_LEVEL.get().copied().unwrap_or(LevelFilter::INFO)
+    CURRENT_LOG_LEVEL.read().map(|level| *level).unwrap_or(LevelFilter::INFO)
+}
+
+/// Returns the absolute path of the log file, if file logging is enabled.
+pub fn log_file() -> Option<&'static Path> {
+    LOG_FILE.get().map(PathBuf::as_path)
+}
+
+/// Returns the directory containing the log file, if file logging is enabled.
+pub fn log_dir() -> Option<&'static Path> {
+    log_file().and_then(Path::parent)
+}
+
+/// Changes the workspace log level of an already initialized logger.
+///
+/// Fails if [`setup_logger`] has not been called yet.
+pub fn set_log_level(level: LevelFilter) -> Result<()> {
+    let handle = FILTER_HANDLE.get().context("Logger is not initialized")?;
+    handle.reload(workspace_filter(level)?).context("Failed to reload log filter")?;
+
+    if let Ok(mut current) = CURRENT_LOG_LEVEL.write() {
+        *current = level;
+    }
+
+    Ok(())
+}
+
+fn workspace_filter(level: LevelFilter) -> Result<EnvFilter> {
+    let workspace_name = env!("WORKSPACE_NAME");
+    Ok(EnvFilter::builder()
+        .with_default_directive(LevelFilter::OFF.into())
+        .parse(format!("{}={}", workspace_name, level))?)
 }
 
 /// Sets up a workspace-scoped logger with optional file output.
@@ -43,13 +191,18 @@ pub fn get_log_level() -> LevelFilter {
 /// # Arguments
 /// * `level` - Optional log level filter. If `None`, reads from `RUST_LOG` environment variable.
 /// * `file` - Optional file path to write logs to. If `Some`, logs are appended to the file.
+/// * `rotation` - Optional size-based rotation for `file`. Ignored when `file` is `None`.
 ///
 /// # Environment Variables
 /// * `RUST_LOG` - Used when `level` is `None` to determine log level
 /// * `WORKSPACE_NAME` - Required. Prefix to filter workspace packages (from `.cargo/config.toml`)
-pub fn setup_logger<P: AsRef<Path>>(level: Option<LevelFilter>, file: Option<P>) -> Result<()> {
-    let workspace_name = env!("WORKSPACE_NAME");
-
+///
+/// The level can be changed later with [`set_log_level`].
+pub fn setup_logger<P: AsRef<Path>>(
+    level: Option<LevelFilter>,
+    file: Option<P>,
+    rotation: Option<LogRotation>,
+) -> Result<()> {
     let base_level = level.unwrap_or_else(|| {
         std::env::var("RUST_LOG")
             .ok()
@@ -57,11 +210,12 @@ pub fn setup_logger<P: AsRef<Path>>(level: Option<LevelFilter>, file: Option<P>)
             .unwrap_or(LevelFilter::INFO)
     });
 
-    CURRENT_LOG_LEVEL.set(base_level).ok();
+    if let Ok(mut current) = CURRENT_LOG_LEVEL.write() {
+        *current = base_level;
+    }
 
-    let filter = EnvFilter::builder()
-        .with_default_directive(LevelFilter::OFF.into())
-        .parse(format!("{}={}", workspace_name, base_level))?;
+    let (filter, filter_handle) = reload::Layer::new(workspace_filter(base_level)?);
+    FILTER_HANDLE.set(filter_handle).ok();
 
     let timer = fmt::time::ChronoLocal::new("%Y-%m-%d %H:%M:%S".to_owned());
 
@@ -78,7 +232,8 @@ pub fn setup_logger<P: AsRef<Path>>(level: Option<LevelFilter>, file: Option<P>)
             std::fs::create_dir_all(parent)?;
         }
 
-        let file = std::fs::OpenOptions::new().create(true).append(true).open(file_path)?;
+        let file = LogFile::open(file_path, rotation).context("Failed to open log file")?;
+        LOG_FILE.set(std::path::absolute(file_path)?).ok();
         let writer = MutexWriter(Arc::new(Mutex::new(file)));
 
         let file_layer = fmt::layer()
@@ -90,9 +245,87 @@ pub fn setup_logger<P: AsRef<Path>>(level: Option<LevelFilter>, file: Option<P>)
             .with_line_number(true);
 
         tracing_subscriber::registry().with(filter).with(console_layer).with(file_layer).init();
+
+        if let Some(rotation) = rotation {
+            tracing::debug!(
+                "Log file rotates at {}, keeping {} backups",
+                format_bytes(rotation.max_size),
+                rotation.max_backups
+            );
+        }
     } else {
         tracing_subscriber::registry().with(filter).with(console_layer).init();
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("{}-{}-{}", env!("WORKSPACE_NAME"), std::process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("app.log")
+    }
+
+    // The only test that installs the global logger; it can be set up once per process.
+    #[test]
+    fn set_log_level_changes_what_is_emitted() {
+        let path = temp_log_path("level");
+        setup_logger(Some(LevelFilter::INFO), Some(&path), None).unwrap();
+
+        tracing::debug!(target: env!("WORKSPACE_NAME"), "debug before raising the level");
+        set_log_level(LevelFilter::DEBUG).unwrap();
+        tracing::debug!(target: env!("WORKSPACE_NAME"), "debug after raising the level");
+        set_log_level(LevelFilter::WARN).unwrap();
+        tracing::info!(target: env!("WORKSPACE_NAME"), "info after lowering the level");
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("debug before raising the level"));
+        assert!(contents.contains("debug after raising the level"));
+        assert!(!contents.contains("info after lowering the level"));
+        assert_eq!(get_log_level(), LevelFilter::WARN);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn log_file_rotates_and_keeps_backups() {
+        let path = temp_log_path("rotation");
+        let rotation = LogRotation { max_size: 10, max_backups: 2 };
+        let mut log_file = LogFile::open(&path, Some(rotation)).unwrap();
+
+        for line in ["first line\n", "second line\n", "third line\n", "fourth line\n"] {
+            log_file.write_all(line.as_bytes()).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth line\n");
+        assert_eq!(fs::read_to_string(log_file.backup_path(1)).unwrap(), "third line\n");
+        assert_eq!(fs::read_to_string(log_file.backup_path(2)).unwrap(), "second line\n");
+        assert!(!log_file.backup_path(3).exists());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn failed_rotation_keeps_logging_to_the_current_file() {
+        let path = temp_log_path("failed-rotation");
+        let rotation = LogRotation { max_size: 10, max_backups: 1 };
+        let mut log_file = LogFile::open(&path, Some(rotation)).unwrap();
+        // A non-empty directory in place of the backup makes the rename fail.
+        let blocker = log_file.backup_path(1);
+        fs::create_dir_all(blocker.join("occupied")).unwrap();
+
+        log_file.write_all(b"first line\n").unwrap();
+        log_file.write_all(b"second line\n").unwrap();
+        log_file.write_all(b"third line\n").unwrap();
+
+        assert!(log_file.rotation.is_none());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "first line\nsecond line\nthird line\n"
+        );
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
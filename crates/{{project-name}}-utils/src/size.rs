@@ -0,0 +1,64 @@
+const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const SI_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+/// Formats a byte count with binary (1024-based) units, e.g. "1.2 KiB".
+pub fn format_bytes(bytes: u64) -> String {
+    format_with_units(bytes, 1024, &BINARY_UNITS)
+}
+
+/// Formats a byte count with SI (1000-based) units, e.g. "1.2 kB".
+pub fn format_bytes_si(bytes: u64) -> String {
+    format_with_units(bytes, 1000, &SI_UNITS)
+}
+
+fn format_with_units(bytes: u64, base: u64, units: &[&str]) -> String {
+    if bytes < base {
+        return format!("{} {}", bytes, units[0]);
+    }
+
+    // Compare the rounded value so e.g. 1023.99 KiB is shown as "1.0 MiB", not "1024.0 KiB".
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while (value * 10.0).round() / 10.0 >= base as f64 && unit < units.len() - 1 {
+        value /= base as f64;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, units[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_below_one_unit_are_exact() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes_si(999), "999 B");
+    }
+
+    #[test]
+    fn unit_boundaries() {
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(1_048_576), "1.0 MiB");
+        assert_eq!(format_bytes(1_073_741_824), "1.0 GiB");
+        assert_eq!(format_bytes_si(1000), "1.0 kB");
+        assert_eq!(format_bytes_si(1_000_000), "1.0 MB");
+    }
+
+    #[test]
+    fn rounding_near_the_next_unit_promotes_it() {
+        assert_eq!(format_bytes(1_048_575), "1.0 MiB");
+        // 1023.95 KiB is 1_048_524.8 bytes, the first value that rounds up to the next unit.
+        assert_eq!(format_bytes(1_048_525), "1.0 MiB");
+        assert_eq!(format_bytes(1_048_524), "1023.9 KiB");
+        assert_eq!(format_bytes_si(999_999), "1.0 MB");
+    }
+
+    #[test]
+    fn largest_unit_is_not_exceeded() {
+        assert_eq!(format_bytes(u64::MAX), "16.0 EiB");
+    }
+}
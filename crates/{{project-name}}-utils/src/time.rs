@@ -0,0 +1,103 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Formats `then` relative to the current time, e.g. "5m ago" or "2d ago".
+pub fn format_relative(then: SystemTime) -> String {
+    format_relative_to(then, SystemTime::now())
+}
+
+/// Formats `then` relative to `now`.
+///
+/// Timestamps older than a week, or in the future, are formatted as an absolute
+/// `YYYY-MM-DD` date (UTC) instead.
+pub fn format_relative_to(then: SystemTime, now: SystemTime) -> String {
+    let Ok(elapsed) = now.duration_since(then) else {
+        return format_date(then);
+    };
+
+    match elapsed.as_secs() {
+        secs if secs < MINUTE => "just now".to_owned(),
+        secs if secs < HOUR => format!("{}m ago", secs / MINUTE),
+        secs if secs < DAY => format!("{}h ago", secs / HOUR),
+        secs if secs < WEEK => format!("{}d ago", secs / DAY),
+        _ => format_date(then),
+    }
+}
+
+/// Formats `time` as a `YYYY-MM-DD` date in UTC.
+pub fn format_date(time: SystemTime) -> String {
+    let days = time.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO).as_secs() / DAY;
+    let (year, month, day) = civil_from_days(days as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Converts days since the Unix epoch to a proleptic Gregorian `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-01-08 00:00:00 UTC.
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(19_730 * DAY)
+    }
+
+    fn ago(secs: u64) -> String {
+        format_relative_to(now() - Duration::from_secs(secs), now())
+    }
+
+    #[test]
+    fn relative_buckets() {
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(MINUTE - 1), "just now");
+        assert_eq!(ago(MINUTE), "1m ago");
+        assert_eq!(ago(HOUR - 1), "59m ago");
+        assert_eq!(ago(HOUR), "1h ago");
+        assert_eq!(ago(DAY - 1), "23h ago");
+        assert_eq!(ago(DAY), "1d ago");
+        assert_eq!(ago(WEEK - 1), "6d ago");
+    }
+
+    #[test]
+    fn a_week_or_older_is_an_absolute_date() {
+        assert_eq!(ago(WEEK), "2024-01-01");
+        assert_eq!(ago(30 * DAY), "2023-12-09");
+    }
+
+    #[test]
+    fn future_timestamps_are_absolute_dates() {
+        let then = now() + Duration::from_secs(10);
+        assert_eq!(format_relative_to(then, now()), "2024-01-08");
+    }
+
+    #[test]
+    fn civil_from_days_on_known_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(11_017), (2000, 3, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+    }
+
+    #[test]
+    fn date_before_the_epoch_is_clamped() {
+        let before_epoch = UNIX_EPOCH - Duration::from_secs(DAY);
+        assert_eq!(format_date(before_epoch), "1970-01-01");
+    }
+}
@@ -1,18 +1,29 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::app::{
     App,
-    message::{InputEvent, Message as GlobalMessage},
-    state::FeatureMessage,
+    message::{AppMessage, InputEvent, Message as GlobalMessage, SystemMessage},
+    state::{
+        CommandEntry, FeatureMessage, THEMES_PATH, UI_SCALE_RANGE, UI_SCALE_STEP,
+        normalize_ui_scale,
+    },
 };
 
-use {{crate_name}}_utils::locale::Locale;
+use {{crate_name}}_theme::{export_palette, import_palette};
+use {{crate_name}}_utils::{locale::Locale, logging, time::format_relative};
 use iced::{
     Element, Length, Task, Theme, keyboard, mouse,
     theme::Base,
-    widget::{button, column, container, iced, row, scrollable, text},
+    widget::{
+        button, checkbox, column, container, iced, pick_list, row, scrollable, text, text_input,
+    },
     window::Id,
 };
+use tracing::level_filters::LevelFilter;
 
 pub const COL_PADDING: f32 = 10.0;
 pub const COL_SPACING: f32 = 10.0;
@@ -20,29 +31,48 @@ pub const ROW_PADDING: f32 = 10.0;
 pub const ROW_SPACING: f32 = 10.0;
 pub const CONTAINER_PADDING: f32 = 10.0;
 
+const HISTORY_HEIGHT: f32 = 150.0;
+
+const LOG_LEVELS: [LevelFilter; 6] = [
+    LevelFilter::OFF,
+    LevelFilter::ERROR,
+    LevelFilter::WARN,
+    LevelFilter::INFO,
+    LevelFilter::DEBUG,
+    LevelFilter::TRACE,
+];
+
 #[derive(Debug, Clone, Default)]
 pub struct State {
     theme_menu_toggled: bool,
     locale_menu_toggled: bool,
+    import_path: String,
+    /// Theme switches apply to the current window only.
+    window_theme_only: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct Context<'a> {
     feature_state: &'a State,
-    current_theme: &'a str,
+    /// Theme of the window being drawn, as resolved by [`App::resolved_theme_name`].
+    theme_name: &'a str,
     current_locale: &'a str,
+    ui_scale: f32,
     themes: &'a HashMap<String, Theme>,
     locales: &'a HashMap<String, Locale>,
+    command_history: &'a [CommandEntry],
 }
 
 impl<'a> Context<'a> {
-    pub fn new(app: &'a App) -> Self {
+    pub fn new(app: &'a App, window_id: Id) -> Self {
         Self {
             feature_state: &app.features_state.main,
-            current_theme: &app.persistent_state.current_theme,
+            theme_name: app.resolved_theme_name(window_id),
             current_locale: &app.persistent_state.current_locale,
+            ui_scale: app.persistent_state.ui_scale,
             themes: &app.app_state.themes,
             locales: &app.app_state.locales,
+            command_history: &app.persistent_state.command_history,
         }
     }
 }
@@ -52,6 +82,7 @@ pub struct ContextMut<'a> {
     feature_state: &'a mut State,
     current_theme: &'a mut String,
     current_locale: &'a mut String,
+    ui_scale: &'a mut f32,
     themes: &'a mut HashMap<String, Theme>,
     locales: &'a mut HashMap<String, Locale>,
 }
@@ -62,6 +93,7 @@ impl<'a> ContextMut<'a> {
             feature_state: &mut app.features_state.main,
             current_theme: &mut app.persistent_state.current_theme,
             current_locale: &mut app.persistent_state.current_locale,
+            ui_scale: &mut app.persistent_state.ui_scale,
             themes: &mut app.app_state.themes,
             locales: &mut app.app_state.locales,
         }
@@ -70,12 +102,22 @@ impl<'a> ContextMut<'a> {
 
 pub fn init(_ctx: ContextMut<'_>) {}
 
+pub fn on_error(ctx: ContextMut<'_>, _error: &str) -> Task<GlobalMessage> {
+    *ctx.feature_state = State::default();
+    Task::none()
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     ThemeMenuToggle,
     LocaleMenuToggle,
-    ThemeSwitch(String),
+    ThemeSwitch(Id, String),
+    WindowThemeOnlyToggle(Id, bool),
     LocaleSwitch(String),
+    UiScaleChange(f32),
+    ThemeExport,
+    ThemeImportPathChange(String),
+    ThemeImport,
 }
 
 impl From<Message> for GlobalMessage {
@@ -86,15 +128,84 @@ impl From<Message> for GlobalMessage {
 
 pub fn update<'a>(msg: Message, ctx: ContextMut<'a>) -> Task<GlobalMessage> {
     match msg {
-        Message::ThemeSwitch(theme_name) => {
-            *ctx.current_theme = theme_name;
-            Task::done(Message::ThemeMenuToggle.into())
+        Message::ThemeSwitch(window_id, theme_name) => {
+            let switch = if ctx.feature_state.window_theme_only {
+                GlobalMessage::App(AppMessage::SetWindowTheme(window_id, Some(theme_name)))
+            } else {
+                GlobalMessage::System(SystemMessage::SetTheme(theme_name))
+            };
+
+            Task::batch([Task::done(switch), Task::done(Message::ThemeMenuToggle.into())])
+        }
+        Message::WindowThemeOnlyToggle(window_id, window_theme_only) => {
+            ctx.feature_state.window_theme_only = window_theme_only;
+            if window_theme_only {
+                return Task::none();
+            }
+
+            Task::done(GlobalMessage::App(AppMessage::SetWindowTheme(window_id, None)))
         }
         Message::LocaleSwitch(locale_tag) => {
+            if !ctx.locales.contains_key(&locale_tag) {
+                return Task::none();
+            }
+
             *ctx.current_locale = locale_tag;
 
             Task::done(Message::LocaleMenuToggle.into())
         }
+        Message::UiScaleChange(scale) => {
+            *ctx.ui_scale = normalize_ui_scale(scale);
+
+            Task::none()
+        }
+        Message::ThemeExport => {
+            let Some(theme) = ctx.themes.get(ctx.current_theme.as_str()) else {
+                return Task::none();
+            };
+
+            let file_name = format!("{}.toml", theme.name().to_lowercase().replace(' ', "-"));
+            let path = Path::new(THEMES_PATH).join(file_name);
+            match export_palette(theme, &path) {
+                Ok(()) => tracing::info!("Exported theme to \"{}\"", path.display()),
+                Err(err) => tracing::error!("Failed to export theme: {err}"),
+            }
+
+            Task::none()
+        }
+        Message::ThemeImportPathChange(path) => {
+            ctx.feature_state.import_path = path;
+
+            Task::none()
+        }
+        Message::ThemeImport => {
+            let path = PathBuf::from(ctx.feature_state.import_path.trim());
+            let theme = match import_palette(&path) {
+                Ok(theme) => theme,
+                Err(err) => {
+                    tracing::error!("Failed to import theme \"{}\": {err:#}", path.display());
+                    return Task::none();
+                }
+            };
+
+            // Keep a copy next to the other user themes so it is loaded on the next start.
+            if let Some(file_name) = path.file_name() {
+                let target = Path::new(THEMES_PATH).join(file_name);
+                if target.canonicalize().ok() != path.canonicalize().ok()
+                    && let Err(err) =
+                        fs::create_dir_all(THEMES_PATH).and_then(|_| fs::copy(&path, &target))
+                {
+                    tracing::warn!("Failed to copy theme to \"{}\": {err}", target.display());
+                }
+            }
+
+            let theme_name = theme.to_string();
+            tracing::info!("Imported theme \"{}\"", theme_name);
+            ctx.themes.insert(theme_name.clone(), theme);
+            ctx.feature_state.import_path.clear();
+
+            Task::done(GlobalMessage::System(SystemMessage::SetTheme(theme_name)))
+        }
         Message::LocaleMenuToggle => {
             let previous_state = ctx.feature_state.locale_menu_toggled;
             ctx.feature_state.locale_menu_toggled = !previous_state;
@@ -110,11 +221,9 @@ pub fn update<'a>(msg: Message, ctx: ContextMut<'a>) -> Task<GlobalMessage> {
     }
 }
 
-pub fn view<'a>(ctx: Context<'a>, _window_id: Id) -> Element<'a, GlobalMessage> {
+pub fn view<'a>(ctx: Context<'a>, window_id: Id) -> Element<'a, GlobalMessage> {
     let current_locale = ctx.current_locale;
-    let current_theme = ctx.current_theme;
-
-    let theme = ctx.themes.get(current_theme).expect("theme not found");
+    let theme = ctx.themes.get(ctx.theme_name).expect("theme not found");
     let locale = ctx.locales.get(current_locale).expect("locale not found");
 
     let current_locale_tag = locale.as_tag();
@@ -143,7 +252,8 @@ pub fn view<'a>(ctx: Context<'a>, _window_id: Id) -> Element<'a, GlobalMessage>
                 button(text(name).width(Length::Fill).center())
                     .width(Length::Fill)
                     .on_press_maybe(
-                        (*name != theme_name).then_some(Message::ThemeSwitch(name.clone()).into()),
+                        (*name != theme_name)
+                            .then_some(Message::ThemeSwitch(window_id, name.clone()).into()),
                     )
                     .style(|_, status| {
                         let extended = theme.extended_palette();
@@ -194,13 +304,83 @@ pub fn view<'a>(ctx: Context<'a>, _window_id: Id) -> Element<'a, GlobalMessage>
     };
 
 
-    let theme_switch_area = column![text(get_string("theme_label")), theme_switcher];
+    let ui_scale = ctx.ui_scale;
+    let scale_control = row![
+        button(text("-")).on_press_maybe(
+            (ui_scale > *UI_SCALE_RANGE.start())
+                .then_some(Message::UiScaleChange(ui_scale - UI_SCALE_STEP).into()),
+        ),
+        text(format!("{:.0}%", ui_scale * 100.0)),
+        button(text("+")).on_press_maybe(
+            (ui_scale < *UI_SCALE_RANGE.end())
+                .then_some(Message::UiScaleChange(ui_scale + UI_SCALE_STEP).into()),
+        ),
+    ]
+    .spacing(ROW_SPACING);
+
+    let open_logs_button = button(text(get_string("open_logs_label"))).on_press_maybe(
+        logging::log_dir()
+            .map(|dir| GlobalMessage::System(SystemMessage::OpenPath(dir.to_path_buf()))),
+    );
+
+    let theme_export_button =
+        button(text(get_string("export_theme_label"))).on_press(Message::ThemeExport.into());
+
+    let import_path = &ctx.feature_state.import_path;
+    let theme_import_row = row![
+        text_input(get_string("import_path_placeholder"), import_path)
+            .on_input(|path| Message::ThemeImportPathChange(path).into())
+            .on_submit(Message::ThemeImport.into()),
+        button(text(get_string("import_theme_label"))).on_press_maybe(
+            (!import_path.trim().is_empty()).then_some(Message::ThemeImport.into())
+        ),
+    ]
+    .spacing(ROW_SPACING);
+
+    let window_theme_only = checkbox(ctx.feature_state.window_theme_only)
+        .label(get_string("window_theme_only_label"))
+        .on_toggle(move |checked| Message::WindowThemeOnlyToggle(window_id, checked).into());
+
+    let theme_switch_area = column![
+        text(get_string("theme_label")),
+        theme_switcher,
+        window_theme_only,
+        theme_export_button,
+        theme_import_row
+    ];
     let locale_switch_area = column![text(get_string("locale_label")), locale_switcher];
+    let scale_area = column![text(get_string("scale_label")), scale_control];
+    let log_level_area = column![
+        text(get_string("log_level_label")),
+        pick_list(LOG_LEVELS, Some(logging::get_log_level()), |level| {
+            GlobalMessage::System(SystemMessage::SetLogLevel(level))
+        }),
+    ];
+
+    let control_row = row![theme_switch_area, locale_switch_area, scale_area, log_level_area]
+        .padding(ROW_PADDING)
+        .spacing(ROW_SPACING);
 
-    let control_row =
-        row![theme_switch_area, locale_switch_area].padding(ROW_PADDING).spacing(ROW_SPACING);
+    let history_area = (!ctx.command_history.is_empty()).then(|| {
+        let entries = ctx.command_history.iter().map(|entry| {
+            let executed_at = text(format_relative(entry.executed_time()));
+            button(row![text(entry.command.to_string()), executed_at].spacing(ROW_SPACING))
+                .style(button::text)
+                .on_press(GlobalMessage::System(SystemMessage::ExecuteAsync(entry.command.clone())))
+                .into()
+        });
+        column![
+            text(get_string("recent_commands_label")),
+            container(scrollable(column(entries))).max_height(HISTORY_HEIGHT),
+        ]
+    });
 
-    container(column![iced(22), control_row].spacing(COL_SPACING).padding(COL_PADDING))
+    container(
+        column![iced(22), control_row, open_logs_button]
+            .push(history_area)
+            .spacing(COL_SPACING)
+            .padding(COL_PADDING),
+    )
         .center(Length::Fill)
         .padding(CONTAINER_PADDING)
         .into()
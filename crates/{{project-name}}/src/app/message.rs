@@ -1,7 +1,12 @@
 use crate::app::state::{FeatureMessage, Window};
-use {{crate_name}}_utils::command::Command;
+use {{crate_name}}_utils::command::{Command, CommandOutput};
 
-use iced::{keyboard::Event as KeyboardEvent, mouse::Event as MouseEvent, window::Id};
+use iced::{
+    Rectangle, keyboard::Event as KeyboardEvent, mouse::Event as MouseEvent, window::Id,
+};
+use std::path::PathBuf;
+
+use tracing::level_filters::LevelFilter;
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -13,7 +18,20 @@ pub enum Message {
 #[derive(Debug, Clone)]
 pub enum AppMessage {
     View(Window),
+    /// Opens a window centered on `anchor`, or on the screen without one.
+    Open(Window, Option<Rectangle>),
     Hide(Id),
+    /// A window gained focus; new windows open over the last focused one.
+    WindowFocused(Id),
+    /// Escape was pressed in a window and not consumed by any widget.
+    Dismiss(Id),
+    /// Overrides the theme of one window, or clears the override with `None`.
+    SetWindowTheme(Id, Option<String>),
+    SetBusy(Option<String>),
+    /// Advances the busy spinner.
+    SpinnerTick,
+    /// Hides the error banner of a feature that failed.
+    DismissFeatureError(&'static str),
     Input(Id, InputEvent),
 }
 
@@ -23,9 +41,146 @@ pub enum InputEvent {
     Keyboard(KeyboardEvent),
 }
 
-#[expect(unused)]
+// Nothing in the template sends some of these yet; the tests build every variant.
+#[cfg_attr(not(test), expect(unused))]
 #[derive(Debug, Clone)]
 pub enum SystemMessage {
     Execute(Command),
+    /// Runs the command on a background thread without blocking the UI.
+    ExecuteAsync(Command),
+    /// Result of an [`SystemMessage::ExecuteAsync`] command, tagged with its task generation.
+    ExecuteFinished(Command, u64, Result<CommandOutput, String>),
+    /// Stops waiting for an [`SystemMessage::ExecuteAsync`] command. The process is not killed.
+    CancelCommand(Command),
+    /// Opens a file or directory in the system file manager.
+    OpenPath(PathBuf),
+    CopyToClipboard(String),
+    /// Reads the clipboard and delivers its contents, if any, as the message built by the callback.
+    ClipboardRead(fn(Option<String>) -> Message),
+    SetLogLevel(LevelFilter),
+    /// Switches the global theme and saves the choice right away.
+    SetTheme(String),
+    ReloadResources,
+    /// Writes persistent state if it changed since the last save.
+    Autosave,
+    /// Saves state and quits regardless of which windows are open.
     Exit,
 }
+
+impl Message {
+    /// Stable name of the message variant, without its payload. Safe to log.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Message::App(msg) => msg.variant_name(),
+            Message::System(msg) => msg.variant_name(),
+            Message::Feature(msg) => msg.variant_name(),
+        }
+    }
+}
+
+impl AppMessage {
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            AppMessage::View(_) => "App::View",
+            AppMessage::Open(..) => "App::Open",
+            AppMessage::Hide(_) => "App::Hide",
+            AppMessage::WindowFocused(_) => "App::WindowFocused",
+            AppMessage::Dismiss(_) => "App::Dismiss",
+            AppMessage::SetWindowTheme(..) => "App::SetWindowTheme",
+            AppMessage::SetBusy(_) => "App::SetBusy",
+            AppMessage::SpinnerTick => "App::SpinnerTick",
+            AppMessage::DismissFeatureError(_) => "App::DismissFeatureError",
+            AppMessage::Input(..) => "App::Input",
+        }
+    }
+}
+
+impl SystemMessage {
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            SystemMessage::Execute(_) => "System::Execute",
+            SystemMessage::ExecuteAsync(_) => "System::ExecuteAsync",
+            SystemMessage::ExecuteFinished(..) => "System::ExecuteFinished",
+            SystemMessage::CancelCommand(_) => "System::CancelCommand",
+            SystemMessage::OpenPath(_) => "System::OpenPath",
+            SystemMessage::CopyToClipboard(_) => "System::CopyToClipboard",
+            SystemMessage::ClipboardRead(_) => "System::ClipboardRead",
+            SystemMessage::SetLogLevel(_) => "System::SetLogLevel",
+            SystemMessage::SetTheme(_) => "System::SetTheme",
+            SystemMessage::ReloadResources => "System::ReloadResources",
+            SystemMessage::Autosave => "System::Autosave",
+            SystemMessage::Exit => "System::Exit",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::app::features::main;
+
+    use std::collections::HashSet;
+
+    use iced::mouse;
+
+    fn every_message() -> Vec<(Message, &'static str)> {
+        let id = Id::unique();
+        let cmd = Command::new("true");
+        let output = CommandOutput { code: Some(0), stdout: String::new(), stderr: String::new() };
+        let input = InputEvent::Mouse(mouse::Event::CursorEntered);
+
+        let app = [
+            (AppMessage::View(Window::Main), "App::View"),
+            (AppMessage::Open(Window::Main, None), "App::Open"),
+            (AppMessage::Hide(id), "App::Hide"),
+            (AppMessage::WindowFocused(id), "App::WindowFocused"),
+            (AppMessage::Dismiss(id), "App::Dismiss"),
+            (AppMessage::SetWindowTheme(id, None), "App::SetWindowTheme"),
+            (AppMessage::SetBusy(None), "App::SetBusy"),
+            (AppMessage::SpinnerTick, "App::SpinnerTick"),
+            (AppMessage::DismissFeatureError("Main"), "App::DismissFeatureError"),
+            (AppMessage::Input(id, input), "App::Input"),
+        ];
+        let system = [
+            (SystemMessage::Execute(cmd.clone()), "System::Execute"),
+            (SystemMessage::ExecuteAsync(cmd.clone()), "System::ExecuteAsync"),
+            (SystemMessage::ExecuteFinished(cmd.clone(), 1, Ok(output)), "System::ExecuteFinished"),
+            (SystemMessage::CancelCommand(cmd), "System::CancelCommand"),
+            (SystemMessage::OpenPath(PathBuf::from(".")), "System::OpenPath"),
+            (SystemMessage::CopyToClipboard(String::new()), "System::CopyToClipboard"),
+            (
+                SystemMessage::ClipboardRead(|_| Message::System(SystemMessage::Exit)),
+                "System::ClipboardRead",
+            ),
+            (SystemMessage::SetLogLevel(LevelFilter::INFO), "System::SetLogLevel"),
+            (SystemMessage::SetTheme("Dark".to_owned()), "System::SetTheme"),
+            (SystemMessage::ReloadResources, "System::ReloadResources"),
+            (SystemMessage::Autosave, "System::Autosave"),
+            (SystemMessage::Exit, "System::Exit"),
+        ];
+        let feature = [(FeatureMessage::Main(main::Message::ThemeMenuToggle), "Feature::Main")];
+
+        app.into_iter()
+            .map(|(msg, name)| (Message::App(msg), name))
+            .chain(system.into_iter().map(|(msg, name)| (Message::System(msg), name)))
+            .chain(feature.into_iter().map(|(msg, name)| (Message::Feature(msg), name)))
+            .collect()
+    }
+
+    #[test]
+    fn variant_names_are_stable() {
+        for (message, name) in every_message() {
+            assert_eq!(message.variant_name(), name);
+        }
+    }
+
+    #[test]
+    fn variant_names_are_unique() {
+        let messages = every_message();
+        let names: HashSet<_> =
+            messages.iter().map(|(message, _)| message.variant_name()).collect();
+
+        assert_eq!(names.len(), messages.len());
+    }
+}
@@ -1,23 +1,49 @@
 mod features;
 mod message;
 mod state;
+mod tasks;
+#[cfg(test)]
+mod tests;
 
 use {{crate_name}}_persistence::Persistent;
-use {{crate_name}}_utils::locale::{Locale, get_system_locale};
+use {{crate_name}}_theme::{is_dark, merge_themes, read_user_themes};
+use {{crate_name}}_utils::{
+    command::{Command, CommandOutput, open_path},
+    crash,
+    io::read_fonts,
+    locale::{DEFAULT_LOCALE, Locale, get_system_locale},
+    logging,
+};
 
-use crate::app::message::InputEvent;
+use crate::{FONTS, IMAGES, app::message::InputEvent};
 
 use message::{AppMessage, Message, SystemMessage};
 use state::{
-    AppState, FeaturesState, PersistentState, Window, initialize_features, route_feature_update,
+    AppState, FeaturesState, PersistentState, THEMES_PATH, Window, initialize_features,
+    normalize_ui_scale, route_feature_update,
 };
 
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    panic::AssertUnwindSafe,
+    time::Duration,
+};
 
-use iced::{Element, Subscription, Task, Theme, event, theme::Style, widget::space, window};
+use iced::{
+    Alignment, Color, Element, Length, Rectangle, Subscription, Task, Theme, Vector, event,
+    futures::channel::oneshot,
+    keyboard,
+    theme::Style,
+    widget::{button, column, container, opaque, row, space, stack, text},
+    window::{self, Position},
+};
 
 pub const STATE_PATH: &str = "state.toml";
 
+const SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+const SPINNER_SIZE: f32 = 32.0;
+
 #[derive(Default)]
 pub struct App {
     app_state: AppState,
@@ -33,69 +59,244 @@ impl App {
     pub fn new(
         icon: Option<&window::Icon>,
         locales: &HashMap<String, Locale>,
+        no_persist: bool,
+        autosave_interval: Option<Duration>,
     ) -> (Self, Task<Message>) {
         tracing::info!("{:-<50}", "");
         tracing::info!("Initializing application");
 
         let locales = locales.clone();
-        let app_state = AppState::new(icon.cloned(), locales);
-        let mut persistent_state = <Self as Persistent>::read_state(STATE_PATH).unwrap_or_default();
+        let app_state = AppState::new(icon.cloned(), locales, no_persist, autosave_interval);
+        let mut persistent_state = if no_persist {
+            tracing::info!("Persistence disabled, starting from default state");
+            PersistentState::default()
+        } else {
+            <Self as Persistent>::read_state(&app_state.state_path).unwrap_or_default()
+        };
         if persistent_state.current_locale.is_empty() {
             persistent_state.current_locale = get_system_locale()
         }
+        persistent_state.ui_scale = normalize_ui_scale(persistent_state.ui_scale);
+
+        let app = Self::with_state(app_state, persistent_state);
+        (app, Task::done(Message::App(AppMessage::View(Window::Main))))
+    }
 
+    /// Builds the application from already loaded state without touching the disk.
+    ///
+    /// A theme or locale that is not loaded is replaced with an available one. Features are
+    /// initialized, but no window is opened.
+    pub fn with_state(app_state: AppState, persistent_state: PersistentState) -> Self {
         let mut app = Self { app_state, persistent_state, ..Default::default() };
+        app.select_available_resources();
+        app.app_state.saved_state = app.persistent_state.clone();
         initialize_features(&mut app);
-        (app, Task::done(Message::App(AppMessage::View(Window::Main))))
+        app
+    }
+
+    fn select_available_resources(&mut self) {
+        let state = &mut self.persistent_state;
+
+        if !self.app_state.themes.contains_key(&state.current_theme) {
+            let fallback = Theme::Dark.to_string();
+            if !state.current_theme.is_empty() {
+                tracing::warn!(
+                    "Theme \"{}\" is not available, using \"{}\"",
+                    state.current_theme,
+                    fallback
+                );
+            }
+            state.current_theme = fallback;
+        }
+
+        if !self.app_state.locales.contains_key(&state.current_locale) {
+            let fallback = if self.app_state.locales.contains_key(DEFAULT_LOCALE) {
+                Some(DEFAULT_LOCALE.to_owned())
+            } else {
+                self.app_state.locales.keys().min().cloned()
+            };
+
+            if let Some(fallback) = fallback {
+                tracing::warn!(
+                    "Locale \"{}\" is not available, using \"{}\"",
+                    state.current_locale,
+                    fallback
+                );
+                state.current_locale = fallback;
+            }
+        }
+    }
+
+    /// Returns `true` if persistent state changed since it was last read or written.
+    pub fn is_dirty(&self) -> bool {
+        self.persistent_state != self.app_state.saved_state
+    }
+
+    fn save_state(&mut self) {
+        if self.app_state.no_persist || !self.is_dirty() {
+            return;
+        }
+
+        match <Self as Persistent>::write_state(&self.app_state.state_path, &self.persistent_state)
+        {
+            Ok(()) => self.app_state.saved_state = self.persistent_state.clone(),
+            Err(e) => tracing::error!("Failed to write state: {}", e),
+        }
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        tracing::trace!("Update: {}", message.variant_name());
         match message {
             Message::Feature(feat_msg) => route_feature_update(self, feat_msg),
             Message::System(sys_msg) => match sys_msg {
                 SystemMessage::Exit => {
-                    if let Err(e) =
-                        <Self as Persistent>::write_state(STATE_PATH, &self.persistent_state)
-                    {
-                        tracing::error!("Failed to write state: {}", e);
-                    };
+                    self.save_state();
 
                     tracing::info!("Exiting application");
                     tracing::info!("{:-<50}", "");
                     iced::exit()
                 }
 
+                SystemMessage::Autosave => {
+                    self.save_state();
+                    Task::none()
+                }
+
                 SystemMessage::Execute(cmd) => {
                     if let Err(err) = cmd.run() {
                         tracing::error!("{err}");
                     } else {
                         tracing::info!("Success: {}", cmd);
                     }
+                    self.persistent_state.push_command(cmd);
                     Task::none()
                 }
-            },
 
-            Message::App(wnd_msg) => match wnd_msg {
-                AppMessage::View(target_window) => {
-                    let active_window =
-                        self.app_state.windows.values().find(|w| *w == &target_window);
-                    if active_window.is_some() {
+                SystemMessage::ExecuteAsync(cmd) => {
+                    tracing::info!("Running in background: {}", cmd);
+                    self.persistent_state.push_command(cmd.clone());
+
+                    let task = Task::future(output_in_background(cmd.clone()));
+                    let (generation, task) =
+                        self.app_state.tasks.track(command_task_key(&cmd), task);
+                    let busy_message =
+                        self.get_string("running_command").replace("{command}", &cmd.to_string());
+                    self.app_state.busy_cancel =
+                        Some(Message::System(SystemMessage::CancelCommand(cmd.clone())));
+                    Task::batch([
+                        Task::done(Message::App(AppMessage::SetBusy(Some(busy_message)))),
+                        task.map(move |result| {
+                            let cmd = cmd.clone();
+                            Message::System(SystemMessage::ExecuteFinished(cmd, generation, result))
+                        }),
+                    ])
+                }
+
+                SystemMessage::ExecuteFinished(cmd, generation, result) => {
+                    if !self.app_state.tasks.is_latest(&command_task_key(&cmd), generation) {
                         return Task::none();
                     }
 
-                    let mut settings = target_window.settings();
-                    if settings.icon.is_none() {
-                        settings.icon = self.app_state.icon.clone();
+                    match result {
+                        Ok(output) if output.success() => tracing::info!("Success: {}", cmd),
+                        Ok(output) => tracing::error!(
+                            "Command failed ({}): {}\n{}",
+                            output.exit_status(),
+                            cmd,
+                            output.stderr.trim_end()
+                        ),
+                        Err(err) => tracing::error!("{err}"),
                     }
+                    Task::done(Message::App(AppMessage::SetBusy(None)))
+                }
+
+                SystemMessage::CancelCommand(cmd) => {
+                    self.app_state.tasks.cancel(&command_task_key(&cmd));
+                    tracing::warn!("Stopped waiting for {}, the process keeps running", cmd);
+                    Task::done(Message::App(AppMessage::SetBusy(None)))
+                }
+
+                SystemMessage::OpenPath(path) => {
+                    Task::future(in_background(move || {
+                        if let Err(err) = open_path(&path) {
+                            tracing::error!("Failed to open \"{}\": {err}", path.display());
+                        }
+                    }))
+                    .discard()
+                }
+
+                SystemMessage::CopyToClipboard(contents) => iced::clipboard::write(contents),
+
+                SystemMessage::ClipboardRead(on_read) => iced::clipboard::read().map(on_read),
 
-                    let (id, task) = window::open(settings);
-                    self.app_state.windows.insert(id, target_window);
-                    if Window::Main == target_window {
-                        self.app_state.main_window_id = Some(id);
+                SystemMessage::ReloadResources => {
+                    tracing::info!("Reloading resources");
+                    match read_user_themes(THEMES_PATH) {
+                        Ok(user_themes) => self.reload_themes(user_themes),
+                        Err(err) => tracing::error!("Failed to reload themes: {err}"),
                     }
-                    task.discard()
+
+                    match read_fonts(*FONTS) {
+                        Ok(fonts) => Task::batch(fonts.into_iter().map(|font| {
+                            iced::font::load(font).then(|result| {
+                                if let Err(err) = result {
+                                    tracing::error!("Failed to reload font: {err:?}");
+                                }
+                                Task::none()
+                            })
+                        })),
+                        Err(err) => {
+                            tracing::error!("Failed to reload fonts: {err}");
+                            Task::none()
+                        }
+                    }
+                }
+
+                SystemMessage::SetTheme(theme_name) => {
+                    if !self.app_state.themes.contains_key(&theme_name) {
+                        tracing::warn!("Unknown theme \"{}\" requested", theme_name);
+                        return Task::none();
+                    }
+
+                    self.persistent_state.current_theme = theme_name;
+                    self.save_state();
+                    Task::none()
+                }
+
+                SystemMessage::SetLogLevel(level) => {
+                    match logging::set_log_level(level) {
+                        Ok(()) => tracing::info!("Log level set to {}", level),
+                        Err(err) => tracing::error!("Failed to set log level: {err}"),
+                    }
+                    Task::none()
+                }
+            },
+
+            Message::App(wnd_msg) => match wnd_msg {
+                AppMessage::View(target_window) => {
+                    if let Some(active_id) = self.find_window(target_window) {
+                        return window::gain_focus(active_id);
+                    }
+
+                    let anchor_id = self.app_state.focused_window.filter(|_| {
+                        matches!(target_window.settings().position, Position::Default)
+                    });
+                    let Some(anchor_id) = anchor_id else {
+                        return self.open_window(target_window, None);
+                    };
+
+                    // Window bounds can only be queried asynchronously, so the window is opened
+                    // from a follow-up message once the focused window's position and size arrive.
+                    window::position(anchor_id).then(move |position| {
+                        window::size(anchor_id).map(move |size| {
+                            let anchor = position.map(|position| Rectangle::new(position, size));
+                            Message::App(AppMessage::Open(target_window, anchor))
+                        })
+                    })
                 }
 
+                AppMessage::Open(target_window, anchor) => self.open_window(target_window, anchor),
+
                 AppMessage::Hide(target_id) => {
                     let Some(main_id) = self.app_state.main_window_id else {
                         return Task::none();
@@ -104,6 +305,11 @@ impl App {
                     if self.app_state.windows.remove(&target_id).is_none() {
                         return Task::none();
                     }
+                    self.app_state.window_order.retain(|id| *id != target_id);
+                    self.app_state.window_themes.remove(&target_id);
+                    if self.app_state.focused_window == Some(target_id) {
+                        self.app_state.focused_window = None;
+                    }
 
                     if self.app_state.windows.is_empty() || target_id == main_id {
                         Task::done(Message::System(SystemMessage::Exit))
@@ -112,8 +318,66 @@ impl App {
                     }
                 }
 
+                AppMessage::WindowFocused(target_id) => {
+                    if self.app_state.windows.contains_key(&target_id) {
+                        self.app_state.focused_window = Some(target_id);
+                    }
+                    Task::none()
+                }
+
+                AppMessage::SetWindowTheme(target_id, theme_name) => {
+                    if !self.app_state.windows.contains_key(&target_id) {
+                        return Task::none();
+                    }
+
+                    let Some(theme_name) = theme_name else {
+                        self.app_state.window_themes.remove(&target_id);
+                        return Task::none();
+                    };
+
+                    if !self.app_state.themes.contains_key(&theme_name) {
+                        tracing::warn!("Unknown theme \"{}\" requested for window", theme_name);
+                        return Task::none();
+                    }
+
+                    self.app_state.window_themes.insert(target_id, theme_name);
+                    Task::none()
+                }
+
+                AppMessage::SetBusy(busy_message) => {
+                    if busy_message.is_none() {
+                        self.app_state.busy_cancel = None;
+                    }
+                    self.app_state.busy = busy_message;
+                    self.app_state.busy_frame = 0;
+                    Task::none()
+                }
+
+                AppMessage::DismissFeatureError(feature) => {
+                    self.app_state.feature_errors.remove(feature);
+                    Task::none()
+                }
+
+                AppMessage::SpinnerTick => {
+                    self.app_state.busy_frame = self.app_state.busy_frame.wrapping_add(1);
+                    Task::none()
+                }
+
+                AppMessage::Dismiss(target_id) => {
+                    let esc_closes =
+                        self.app_state.windows.get(&target_id).is_some_and(Window::esc_closes);
+                    if esc_closes {
+                        Task::done(Message::App(AppMessage::Hide(target_id)))
+                    } else {
+                        Task::none()
+                    }
+                }
 
                 AppMessage::Input(window_id, input) => {
+                    if self.app_state.busy.is_some() {
+                        return Task::none();
+                    }
+
                     let Some(target_window) = self.app_state.windows.get(&window_id) else {
                         return Task::none();
                     };
@@ -123,21 +387,134 @@ impl App {
         }
     }
 
-    pub fn view<'a>(&'a self, id: window::Id) -> Element<'a, Message> {
+    /// Replaces the loaded themes, keeping the previous version of any theme still in use.
+    fn reload_themes(&mut self, user_themes: Vec<Theme>) {
+        let mut themes = merge_themes(user_themes);
+        let active_themes = std::iter::once(&self.persistent_state.current_theme)
+            .chain(self.app_state.window_themes.values());
+
+        for name in active_themes {
+            if themes.contains_key(name) {
+                continue;
+            }
+            if let Some(theme) = self.app_state.themes.get(name) {
+                tracing::warn!("Theme \"{}\" failed to reload, keeping the loaded version", name);
+                themes.insert(name.clone(), theme.clone());
+            }
+        }
+
+        self.app_state.themes = themes;
+    }
+
+    fn find_window(&self, target_window: Window) -> Option<window::Id> {
         self.app_state
+            .window_order
+            .iter()
+            .rev()
+            .find(|id| self.app_state.windows.get(id) == Some(&target_window))
+            .copied()
+    }
+
+    /// Opens `target_window`, centered over `anchor` when given, or focuses it if already open.
+    fn open_window(&mut self, target_window: Window, anchor: Option<Rectangle>) -> Task<Message> {
+        if let Some(active_id) = self.find_window(target_window) {
+            return window::gain_focus(active_id);
+        }
+
+        let mut settings = target_window.settings();
+        if settings.icon.is_none() {
+            settings.icon = target_window
+                .icon_file()
+                .and_then(load_window_icon)
+                .or_else(|| self.app_state.icon.clone());
+        }
+
+        if let Some(anchor) = anchor {
+            let half_size = Vector::new(settings.size.width / 2.0, settings.size.height / 2.0);
+            settings.position = Position::Specific(anchor.center() - half_size);
+        } else if matches!(settings.position, Position::Default) {
+            settings.position = Position::Centered;
+        }
+
+        let (id, task) = window::open(settings);
+        self.app_state.windows.insert(id, target_window);
+        self.app_state.window_order.push(id);
+        if Window::Main == target_window {
+            self.app_state.main_window_id = Some(id);
+        }
+        task.discard()
+    }
+
+    pub fn view<'a>(&'a self, id: window::Id) -> Element<'a, Message> {
+        let content = self
+            .app_state
             .windows
             .get(&id)
             .map(|window| window.view(self, id))
-            .unwrap_or(space().into())
+            .unwrap_or(space().into());
+
+        let content = if self.app_state.feature_errors.is_empty() {
+            content
+        } else {
+            let banner = feature_error_banner(
+                &self.app_state.feature_errors,
+                self.get_string("feature_failed"),
+                self.get_string("dismiss_label"),
+            );
+            stack![content, banner].into()
+        };
+
+        match &self.app_state.busy {
+            Some(busy_message) => {
+                let backdrop = if self.theme_is_dark(id) { Color::BLACK } else { Color::WHITE };
+                let cancel = self
+                    .app_state
+                    .busy_cancel
+                    .clone()
+                    .map(|message| (self.get_string("cancel_label"), message));
+                let overlay =
+                    busy_overlay(busy_message, self.app_state.busy_frame, cancel, backdrop);
+                stack![content, overlay].into()
+            }
+            None => content,
+        }
     }
 
+    /// Looks up an app-level string (`app.toml`) in the current locale.
+    fn get_string(&self, key: &str) -> &str {
+        self.app_state
+            .locales
+            .get(&self.persistent_state.current_locale)
+            .map_or("Unknown", |locale| locale.get_string("app", key))
+    }
+
+    /// Resolves the theme of a window, falling back to `Dark` if it is not loaded.
+    pub fn theme(&self, id: window::Id) -> Theme {
+        let theme_name = self.resolved_theme_name(id);
+        self.app_state.themes.get(theme_name).cloned().unwrap_or(Theme::Dark)
+    }
+
+    /// Name of the theme a window uses.
+    ///
+    /// Precedence: per-window override, then the window's preferred theme, then the global theme.
+    pub fn resolved_theme_name(&self, id: window::Id) -> &str {
+        let preferred_theme = self
+            .app_state
+            .windows
+            .get(&id)
+            .and_then(Window::preferred_theme)
+            .filter(|name| self.app_state.themes.contains_key(*name));
 
-    pub fn theme(&self, _: window::Id) -> Theme {
         self.app_state
-            .themes
-            .get(&self.persistent_state.current_theme)
-            .cloned()
-            .unwrap_or(Theme::Dark)
+            .window_themes
+            .get(&id)
+            .map(String::as_str)
+            .or(preferred_theme)
+            .unwrap_or(&self.persistent_state.current_theme)
+    }
+
+    pub fn theme_is_dark(&self, id: window::Id) -> bool {
+        is_dark(&self.theme(id))
     }
 
     pub fn style(&self, theme: &Theme) -> Style {
@@ -145,13 +522,30 @@ impl App {
         Style { background_color: palette.background, text_color: palette.text }
     }
 
+    pub fn scale_factor(&self, _: window::Id) -> f32 {
+        self.persistent_state.ui_scale
+    }
+
     pub fn title(&self, id: window::Id) -> String {
         let window = self.app_state.windows.get(&id).map(|w| w.title()).unwrap_or("");
-        format!("{} - {}", env!("WORKSPACE_NAME"), window)
+        let unsaved_marker = if self.is_dirty() && !self.app_state.no_persist { " *" } else { "" };
+        format!("{} - {}{}", env!("WORKSPACE_NAME"), window, unsaved_marker)
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
+        let autosave = self.app_state.autosave_interval.map_or(Subscription::none(), |interval| {
+            iced::time::every(interval).map(|_| Message::System(SystemMessage::Autosave))
+        });
+
+        let spinner = if self.app_state.busy.is_some() {
+            iced::time::every(SPINNER_INTERVAL).map(|_| Message::App(AppMessage::SpinnerTick))
+        } else {
+            Subscription::none()
+        };
+
         Subscription::batch([
+            autosave,
+            spinner,
             event::listen_with(|event, _, window_id| match event {
                 event::Event::Mouse(mouse_event) => {
                     Some(Message::App(AppMessage::Input(window_id, InputEvent::Mouse(mouse_event))))
@@ -162,7 +556,130 @@ impl App {
                 ))),
                 _ => None,
             }),
+            event::listen_with(shortcut),
             window::close_requests().map(|id| Message::App(AppMessage::Hide(id))),
+            window::events().filter_map(|(id, event)| {
+                matches!(event, window::Event::Focused)
+                    .then_some(Message::App(AppMessage::WindowFocused(id)))
+            }),
         ])
     }
 }
+
+/// Maps global keyboard shortcuts to messages.
+fn shortcut(event: event::Event, status: event::Status, window_id: window::Id) -> Option<Message> {
+    match event {
+        event::Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::Escape),
+            ..
+        }) if status == event::Status::Ignored => {
+            Some(Message::App(AppMessage::Dismiss(window_id)))
+        }
+        event::Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::F5),
+            ..
+        }) => Some(Message::System(SystemMessage::ReloadResources)),
+        event::Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Character(character),
+            modifiers,
+            ..
+        }) if modifiers.command() && character.as_str() == "q" => {
+            Some(Message::System(SystemMessage::Exit))
+        }
+        _ => None,
+    }
+}
+
+/// Lists failed features; `failed` has `{feature}` and `{error}` placeholders.
+fn feature_error_banner<'a>(
+    errors: &'a BTreeMap<&'static str, String>,
+    failed: &'a str,
+    dismiss: &'a str,
+) -> Element<'a, Message> {
+    let rows = errors.iter().map(|(&feature, error)| {
+        let message = failed.replace("{feature}", feature).replace("{error}", error);
+        row![
+            text(message).width(Length::Fill),
+            button(text(dismiss))
+                .on_press(Message::App(AppMessage::DismissFeatureError(feature))),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    });
+
+    container(column(rows).spacing(5)).padding(10).width(Length::Fill).style(|theme: &Theme| {
+        let danger = theme.extended_palette().danger.base;
+        container::Style {
+            background: Some(danger.color.into()),
+            text_color: Some(danger.text),
+            ..Default::default()
+        }
+    })
+    .into()
+}
+
+/// Blocking overlay with a spinner, dimming the window towards `backdrop`.
+///
+/// `cancel` adds a button with the given label that sends the message.
+fn busy_overlay<'a>(
+    busy_message: &'a str,
+    frame: usize,
+    cancel: Option<(&'a str, Message)>,
+    backdrop: Color,
+) -> Element<'a, Message> {
+    let spinner = text(SPINNER_FRAMES[frame % SPINNER_FRAMES.len()]).size(SPINNER_SIZE);
+    let content = column![spinner, text(busy_message)]
+        .push(cancel.map(|(label, message)| button(text(label)).on_press(message)))
+        .spacing(10)
+        .align_x(Alignment::Center);
+
+    opaque(container(content).center(Length::Fill).style(move |theme: &Theme| {
+        let palette = theme.palette();
+        container::Style {
+            background: Some(backdrop.scale_alpha(0.6).into()),
+            text_color: Some(palette.text),
+            ..Default::default()
+        }
+    }))
+}
+
+/// Runs a feature update, turning a panic inside it into the panic message.
+///
+/// Lives here rather than in `register_features!`, whose file is not rendered by the template.
+fn catch_feature_panic<T>(update: impl FnOnce() -> T) -> Result<T, String> {
+    crash::catch_recoverable(AssertUnwindSafe(update))
+        .map_err(|payload| crash::panic_payload(payload.as_ref()).to_owned())
+}
+
+fn command_task_key(cmd: &Command) -> String {
+    format!("execute:{cmd}")
+}
+
+/// Runs `cmd` on a dedicated thread so the blocking wait never stalls the runtime.
+async fn output_in_background(cmd: Command) -> Result<CommandOutput, String> {
+    in_background(move || cmd.output().map_err(|err| format!("{err:#}"))).await?
+}
+
+/// Runs blocking `work` on a dedicated thread and resolves with its result.
+async fn in_background<T: Send + 'static>(
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, String> {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(work());
+    });
+    receiver.await.map_err(|_| "Background thread exited without a result".to_owned())
+}
+
+fn load_window_icon(file_name: &str) -> Option<window::Icon> {
+    let icon_path = IMAGES.join(file_name);
+    window::icon::from_file(&icon_path)
+        .inspect_err(|e| {
+            tracing::error!(
+                "Failed to load window icon \"{}\": {e}",
+                &icon_path.to_str().unwrap_or_default()
+            )
+        })
+        .ok()
+}
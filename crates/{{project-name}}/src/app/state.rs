@@ -1,37 +1,140 @@
 use crate::{
-    app::features::main,
+    app::{STATE_PATH, features::main, message::Message, tasks::TaskRegistry},
     macros::{register_features, register_windows},
 };
 
 use {{crate_name}}_theme::load_available_themes;
-use {{crate_name}}_utils::locale::Locale;
+use {{crate_name}}_utils::{command::Command, locale::Locale};
 use iced::{
     Size, Theme,
     window::{Icon, Id, Settings},
 };
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::RangeInclusive,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const THEMES_PATH: &str = "themes";
 
-const THEMES_PATH: &str = "themes";
+pub const UI_SCALE_RANGE: RangeInclusive<f32> = 0.5..=3.0;
+pub const UI_SCALE_STEP: f32 = 0.1;
+pub const COMMAND_HISTORY_CAPACITY: usize = 20;
 
 #[derive(Debug, Clone, Default)]
 pub struct AppState {
     pub icon: Option<Icon>,
     pub main_window_id: Option<Id>,
     pub windows: HashMap<Id, Window>,
+    /// Open window ids in the order they were opened, oldest first.
+    pub window_order: Vec<Id>,
+    /// Last window that gained focus, used to place new windows.
+    pub focused_window: Option<Id>,
+    /// Per-window theme overrides, taking precedence over the global theme.
+    pub window_themes: HashMap<Id, String>,
     pub themes: HashMap<String, Theme>,
     pub locales: HashMap<String, Locale>,
+    /// Skip reading and writing persistent state (`--no-persist`).
+    pub no_persist: bool,
+    /// File persistent state is read from and written to.
+    pub state_path: PathBuf,
+    /// Interval of `SystemMessage::Autosave`, if enabled in the config.
+    pub autosave_interval: Option<Duration>,
+    /// Persistent state as last read from or written to disk.
+    pub saved_state: PersistentState,
+    pub tasks: TaskRegistry,
+    /// Message shown in a blocking overlay over every window while set.
+    pub busy: Option<String>,
+    /// Frame of the busy spinner, advanced while `busy` is set.
+    pub busy_frame: usize,
+    /// Message sent by the cancel button of the busy overlay. Cleared together with `busy`.
+    pub busy_cancel: Option<Message>,
+    /// Errors of features that panicked while handling a message, keyed by feature name.
+    pub feature_errors: BTreeMap<&'static str, String>,
 }
 
 impl AppState {
-    pub fn new(icon: Option<Icon>, locales: HashMap<String, Locale>) -> Self {
-        Self { themes: load_available_themes(THEMES_PATH), icon, locales, ..Default::default() }
+    pub fn new(
+        icon: Option<Icon>,
+        locales: HashMap<String, Locale>,
+        no_persist: bool,
+        autosave_interval: Option<Duration>,
+    ) -> Self {
+        Self {
+            themes: load_available_themes(THEMES_PATH),
+            icon,
+            locales,
+            no_persist,
+            state_path: PathBuf::from(STATE_PATH),
+            autosave_interval,
+            ..Default::default()
+        }
     }
 }
 
-#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PersistentState {
     pub current_theme: String,
     pub current_locale: String,
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+    /// Executed commands, most recent first.
+    #[serde(default)]
+    pub command_history: Vec<CommandEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CommandEntry {
+    pub command: Command,
+    /// Seconds since the Unix epoch.
+    pub executed_at: u64,
+}
+
+impl CommandEntry {
+    pub fn executed_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.executed_at)
+    }
+}
+
+impl PersistentState {
+    /// Records `command` as the most recent history entry.
+    ///
+    /// An earlier identical command is removed, and the history is trimmed to
+    /// [`COMMAND_HISTORY_CAPACITY`] entries.
+    pub fn push_command(&mut self, command: Command) {
+        let executed_at =
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        self.command_history.retain(|entry| entry.command != command);
+        self.command_history.insert(0, CommandEntry { command, executed_at });
+        self.command_history.truncate(COMMAND_HISTORY_CAPACITY);
+    }
+}
+
+impl Default for PersistentState {
+    fn default() -> Self {
+        Self {
+            current_theme: String::new(),
+            current_locale: String::new(),
+            ui_scale: default_ui_scale(),
+            command_history: Vec::new(),
+        }
+    }
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+/// Clamps a UI scale factor to [`UI_SCALE_RANGE`], rounded to [`UI_SCALE_STEP`].
+pub fn normalize_ui_scale(scale: f32) -> f32 {
+    if !scale.is_finite() {
+        return default_ui_scale();
+    }
+
+    let scale = (scale / UI_SCALE_STEP).round() * UI_SCALE_STEP;
+    scale.clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end())
 }
 
 register_features!(main::Main);
@@ -47,3 +150,84 @@ register_windows!(Main {
     input_handler: main::input,
     context: main::Context::new
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(state: &PersistentState) -> Vec<&str> {
+        state.command_history.iter().map(|entry| entry.command.program.as_str()).collect()
+    }
+
+    #[test]
+    fn ui_scale_is_clamped_to_range() {
+        assert_eq!(normalize_ui_scale(0.1), 0.5);
+        assert_eq!(normalize_ui_scale(-2.0), 0.5);
+        assert_eq!(normalize_ui_scale(3.4), 3.0);
+        assert_eq!(normalize_ui_scale(100.0), 3.0);
+    }
+
+    #[test]
+    fn ui_scale_bounds_survive_step_rounding() {
+        assert_eq!(normalize_ui_scale(0.5), 0.5);
+        assert_eq!(normalize_ui_scale(3.0), 3.0);
+        assert_eq!(normalize_ui_scale(0.5 - UI_SCALE_STEP), 0.5);
+        assert_eq!(normalize_ui_scale(3.0 + UI_SCALE_STEP), 3.0);
+    }
+
+    #[test]
+    fn ui_scale_is_rounded_to_step() {
+        assert!((normalize_ui_scale(1.04) - 1.0).abs() < f32::EPSILON);
+        assert!((normalize_ui_scale(1.06) - 1.1).abs() < 1e-6);
+        assert!((normalize_ui_scale(1.0 + UI_SCALE_STEP) - 1.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn non_finite_ui_scale_resets_to_default() {
+        assert_eq!(normalize_ui_scale(f32::NAN), 1.0);
+        assert_eq!(normalize_ui_scale(f32::INFINITY), 1.0);
+        assert_eq!(normalize_ui_scale(f32::NEG_INFINITY), 1.0);
+    }
+
+    #[test]
+    fn push_command_moves_duplicates_to_the_front() {
+        let mut state = PersistentState::default();
+        state.push_command(Command::new("first"));
+        state.push_command(Command::new("second"));
+        state.push_command(Command::new("first"));
+
+        assert_eq!(history(&state), ["first", "second"]);
+    }
+
+    #[test]
+    fn push_command_keeps_commands_with_different_args() {
+        let mut state = PersistentState::default();
+        state.push_command(Command::new("ls").arg("-a"));
+        state.push_command(Command::new("ls"));
+
+        assert_eq!(state.command_history.len(), 2);
+    }
+
+    #[test]
+    fn push_command_records_the_current_time() {
+        let before = SystemTime::now() - Duration::from_secs(1);
+        let mut state = PersistentState::default();
+        state.push_command(Command::new("ls"));
+
+        let executed = state.command_history[0].executed_time();
+        assert!(executed >= before && executed <= SystemTime::now());
+    }
+
+    #[test]
+    fn push_command_truncates_to_capacity() {
+        let mut state = PersistentState::default();
+        for index in 0..COMMAND_HISTORY_CAPACITY + 5 {
+            state.push_command(Command::new(format!("cmd-{index}")));
+        }
+
+        assert_eq!(state.command_history.len(), COMMAND_HISTORY_CAPACITY);
+        let newest = format!("cmd-{}", COMMAND_HISTORY_CAPACITY + 4);
+        assert_eq!(state.command_history[0].command.program, newest);
+        assert_eq!(state.command_history.last().unwrap().command.program, "cmd-5");
+    }
+}
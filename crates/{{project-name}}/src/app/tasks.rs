@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use iced::{Task, task::Handle};
+
+/// Keyed registry of running tasks.
+///
+/// Each key tracks a generation counter. Registering a new task under a key aborts the task it
+/// replaces and bumps the generation, so results carrying an older generation can be dropped.
+#[derive(Debug, Clone, Default)]
+pub struct TaskRegistry {
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Entry {
+    generation: u64,
+    handle: Option<Handle>,
+}
+
+impl TaskRegistry {
+    /// Registers `task` under `key`, aborting any task previously registered under it.
+    ///
+    /// Returns the generation assigned to the task together with an abortable version of it.
+    /// The caller should tag the task's output with the generation and check it with
+    /// [`TaskRegistry::is_latest`] when the result arrives.
+    pub fn track<T: 'static>(&mut self, key: impl Into<String>, task: Task<T>) -> (u64, Task<T>) {
+        let (task, handle) = task.abortable();
+        let entry = self.entries.entry(key.into()).or_default();
+        if let Some(previous) = entry.handle.replace(handle) {
+            previous.abort();
+        }
+        entry.generation += 1;
+        (entry.generation, task)
+    }
+
+    /// Aborts the task registered under `key` and invalidates its pending result.
+    ///
+    /// Only the task is aborted. Work it handed off outside of the runtime, such as a process
+    /// waited on by a background thread, keeps running to completion.
+    pub fn cancel(&mut self, key: &str) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            if let Some(handle) = entry.handle.take() {
+                handle.abort();
+            }
+            entry.generation += 1;
+        }
+    }
+
+    /// Returns the current generation of `key`, if anything was ever registered under it.
+    pub fn latest(&self, key: &str) -> Option<u64> {
+        self.entries.get(key).map(|entry| entry.generation)
+    }
+
+    /// Returns `true` if `generation` is still the current one for `key`.
+    pub fn is_latest(&self, key: &str, generation: u64) -> bool {
+        self.latest(key) == Some(generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracking_again_makes_the_previous_generation_stale() {
+        let mut registry = TaskRegistry::default();
+        let (first, _) = registry.track("search", Task::<()>::none());
+        let (second, _) = registry.track("search", Task::<()>::none());
+
+        assert!(!registry.is_latest("search", first));
+        assert!(registry.is_latest("search", second));
+        assert_eq!(registry.latest("search"), Some(second));
+    }
+
+    #[test]
+    fn cancel_invalidates_the_pending_result() {
+        let mut registry = TaskRegistry::default();
+        let (generation, _) = registry.track("search", Task::<()>::none());
+
+        registry.cancel("search");
+
+        assert!(!registry.is_latest("search", generation));
+        assert_ne!(registry.latest("search"), Some(generation));
+    }
+
+    #[test]
+    fn keys_are_independent() {
+        let mut registry = TaskRegistry::default();
+        let (search, _) = registry.track("search", Task::<()>::none());
+        let _ = registry.track("import", Task::<()>::none());
+        registry.cancel("import");
+
+        assert!(registry.is_latest("search", search));
+    }
+
+    #[test]
+    fn unknown_keys_have_no_generation() {
+        let mut registry = TaskRegistry::default();
+        registry.cancel("missing");
+
+        assert_eq!(registry.latest("missing"), None);
+        assert!(!registry.is_latest("missing", 0));
+    }
+}
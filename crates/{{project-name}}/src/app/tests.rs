@@ -0,0 +1,555 @@
+use super::*;
+
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use iced::futures::StreamExt;
+use iced_runtime::{Action, task::into_stream, window::Action as WindowAction};
+
+/// Unique path in the system temp directory. Nothing is created.
+fn temp_path(name: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!(
+        "{}-{}-{}-{}",
+        env!("WORKSPACE_NAME"),
+        std::process::id(),
+        n,
+        name
+    ))
+}
+
+/// Polls `task` without a runtime and returns the actions it produced.
+///
+/// Actions waiting on the runtime (e.g. opening a window) never resolve here, so polling stops
+/// once the task stays pending.
+fn actions(task: Task<Message>) -> Vec<Action<Message>> {
+    let Some(mut stream) = into_stream(task) else {
+        return Vec::new();
+    };
+
+    let mut context = Context::from_waker(Waker::noop());
+    let mut actions = Vec::new();
+    let mut pending_polls = 0;
+    loop {
+        match stream.poll_next_unpin(&mut context) {
+            Poll::Ready(Some(action)) => actions.push(action),
+            Poll::Ready(None) => break,
+            Poll::Pending if pending_polls < 16 => pending_polls += 1,
+            Poll::Pending => break,
+        }
+    }
+    actions
+}
+
+fn test_state(no_persist: bool) -> AppState {
+    AppState {
+        themes: merge_themes(Vec::new()),
+        locales: HashMap::from([(DEFAULT_LOCALE.to_owned(), Locale::default())]),
+        no_persist,
+        state_path: temp_path("state.toml"),
+        ..Default::default()
+    }
+}
+
+fn test_app(no_persist: bool) -> App {
+    App::with_state(test_state(no_persist), PersistentState::default())
+}
+
+#[test]
+fn no_persist_never_writes_state() {
+    let mut app = test_app(true);
+    app.persistent_state.ui_scale = 2.0;
+    assert!(app.is_dirty());
+
+    let _ = app.update(Message::System(SystemMessage::Exit));
+
+    assert!(!app.app_state.state_path.exists());
+}
+
+#[test]
+fn exit_writes_state_when_persistence_is_enabled() {
+    let mut app = test_app(false);
+    app.persistent_state.ui_scale = 2.0;
+
+    let _ = app.update(Message::System(SystemMessage::Exit));
+
+    let state_path = app.app_state.state_path.clone();
+    assert!(state_path.exists());
+    assert!(!app.is_dirty());
+    std::fs::remove_file(state_path).unwrap();
+}
+
+#[test]
+fn default_state_selects_loaded_theme_and_locale() {
+    let app = test_app(true);
+
+    assert_eq!(app.persistent_state.current_theme, Theme::Dark.to_string());
+    assert_eq!(app.persistent_state.current_locale, DEFAULT_LOCALE);
+    assert!(!app.is_dirty());
+}
+
+#[test]
+fn unknown_theme_and_locale_fall_back() {
+    let persistent_state = PersistentState {
+        current_theme: "Missing".to_owned(),
+        current_locale: "xx-XX".to_owned(),
+        ..Default::default()
+    };
+    let app = App::with_state(test_state(true), persistent_state);
+
+    assert_eq!(app.persistent_state.current_theme, Theme::Dark.to_string());
+    assert_eq!(app.persistent_state.current_locale, DEFAULT_LOCALE);
+}
+
+#[test]
+fn known_theme_and_locale_are_kept() {
+    let persistent_state = PersistentState {
+        current_theme: Theme::Dracula.to_string(),
+        current_locale: DEFAULT_LOCALE.to_owned(),
+        ..Default::default()
+    };
+    let app = App::with_state(test_state(true), persistent_state);
+
+    assert_eq!(app.persistent_state.current_theme, Theme::Dracula.to_string());
+}
+
+#[test]
+fn reload_keeps_active_themes_that_failed_to_load() {
+    let everforest = Theme::custom("Everforest", Theme::Dark.palette());
+    let preview = Theme::custom("Preview", Theme::Light.palette());
+    let window_id = window::Id::unique();
+
+    let mut app_state = test_state(true);
+    app_state.themes.insert(everforest.to_string(), everforest.clone());
+    app_state.themes.insert(preview.to_string(), preview.clone());
+    app_state.window_themes.insert(window_id, preview.to_string());
+    let persistent_state =
+        PersistentState { current_theme: everforest.to_string(), ..Default::default() };
+    let mut app = App::with_state(app_state, persistent_state);
+
+    app.reload_themes(Vec::new());
+
+    assert_eq!(app.app_state.themes.get("Everforest"), Some(&everforest));
+    assert_eq!(app.app_state.themes.get("Preview"), Some(&preview));
+    assert_eq!(app.theme(window_id), preview);
+}
+
+#[test]
+fn reload_drops_unused_themes_and_picks_up_new_ones() {
+    let stale = Theme::custom("Stale", Theme::Dark.palette());
+    let fresh = Theme::custom("Fresh", Theme::Light.palette());
+
+    let mut app = test_app(true);
+    app.app_state.themes.insert(stale.to_string(), stale);
+
+    app.reload_themes(vec![fresh.clone()]);
+
+    assert!(!app.app_state.themes.contains_key("Stale"));
+    assert_eq!(app.app_state.themes.get("Fresh"), Some(&fresh));
+}
+
+#[test]
+fn viewing_main_registers_the_main_window() {
+    let mut app = test_app(true);
+
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+
+    let main_id = app.app_state.main_window_id.expect("main window id is set");
+    assert_eq!(app.app_state.windows.get(&main_id), Some(&Window::Main));
+    assert_eq!(app.app_state.window_order, vec![main_id]);
+}
+
+#[test]
+fn viewing_an_open_window_focuses_it() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let main_id = app.app_state.main_window_id.unwrap();
+
+    let task = app.update(Message::App(AppMessage::View(Window::Main)));
+
+    assert_eq!(app.app_state.windows.len(), 1);
+    assert_eq!(app.app_state.window_order, vec![main_id]);
+    assert!(matches!(
+        actions(task).as_slice(),
+        [Action::Window(WindowAction::GainFocus(id))] if *id == main_id
+    ));
+}
+
+#[test]
+fn hiding_the_last_window_requests_exit() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let main_id = app.app_state.main_window_id.unwrap();
+
+    let task = app.update(Message::App(AppMessage::Hide(main_id)));
+
+    assert!(app.app_state.windows.is_empty());
+    assert!(app.app_state.window_order.is_empty());
+    assert!(matches!(
+        actions(task).as_slice(),
+        [Action::Output(Message::System(SystemMessage::Exit))]
+    ));
+}
+
+#[test]
+fn exit_without_windows_exits_the_runtime() {
+    let mut app = test_app(true);
+
+    let task = app.update(Message::System(SystemMessage::Exit));
+
+    assert!(matches!(actions(task).as_slice(), [Action::Exit]));
+}
+
+#[test]
+fn find_window_returns_the_most_recent_instance() {
+    let mut app = test_app(true);
+    let (older, newer) = (window::Id::unique(), window::Id::unique());
+    for id in [older, newer] {
+        app.app_state.windows.insert(id, Window::Main);
+        app.app_state.window_order.push(id);
+    }
+    app.app_state.main_window_id = Some(older);
+
+    assert_eq!(app.find_window(Window::Main), Some(newer));
+
+    let _ = app.update(Message::App(AppMessage::Hide(newer)));
+
+    assert_eq!(app.app_state.window_order, vec![older]);
+    assert_eq!(app.find_window(Window::Main), Some(older));
+}
+
+#[test]
+fn saving_clean_state_does_not_write() {
+    let mut app = test_app(false);
+    assert!(!app.is_dirty());
+
+    app.save_state();
+    let _ = app.update(Message::System(SystemMessage::Autosave));
+
+    assert!(!app.app_state.state_path.exists());
+}
+
+#[test]
+fn autosave_writes_only_after_a_change() {
+    let mut app = test_app(false);
+    let state_path = app.app_state.state_path.clone();
+
+    app.persistent_state.ui_scale = 1.5;
+    assert!(app.is_dirty());
+    let _ = app.update(Message::System(SystemMessage::Autosave));
+    assert!(state_path.exists());
+    assert!(!app.is_dirty());
+
+    std::fs::remove_file(&state_path).unwrap();
+    let _ = app.update(Message::System(SystemMessage::Autosave));
+    assert!(!state_path.exists());
+}
+
+#[test]
+fn set_theme_saves_immediately() {
+    let mut app = test_app(false);
+    let state_path = app.app_state.state_path.clone();
+    let dracula = Theme::Dracula.to_string();
+
+    let _ = app.update(Message::System(SystemMessage::SetTheme(dracula.clone())));
+
+    assert_eq!(app.persistent_state.current_theme, dracula);
+    assert!(state_path.exists());
+    assert!(!app.is_dirty());
+    std::fs::remove_file(&state_path).unwrap();
+}
+
+#[test]
+fn set_theme_rejects_unknown_themes() {
+    let mut app = test_app(false);
+    let current_theme = app.persistent_state.current_theme.clone();
+
+    let _ = app.update(Message::System(SystemMessage::SetTheme("Missing".to_owned())));
+
+    assert_eq!(app.persistent_state.current_theme, current_theme);
+    assert!(!app.app_state.state_path.exists());
+}
+
+#[test]
+fn title_marks_unsaved_changes() {
+    let mut app = test_app(false);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let main_id = app.app_state.main_window_id.unwrap();
+    let clean_title = app.title(main_id);
+    assert!(!clean_title.ends_with(" *"));
+
+    app.persistent_state.ui_scale = 2.0;
+    assert_eq!(app.title(main_id), format!("{clean_title} *"));
+
+    app.save_state();
+    assert_eq!(app.title(main_id), clean_title);
+    std::fs::remove_file(&app.app_state.state_path).unwrap();
+}
+
+#[test]
+fn title_has_no_marker_without_persistence() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let main_id = app.app_state.main_window_id.unwrap();
+
+    app.persistent_state.ui_scale = 2.0;
+
+    assert!(!app.title(main_id).ends_with(" *"));
+}
+
+#[test]
+fn finished_command_clears_busy_overlay() {
+    let mut app = test_app(true);
+    let cmd = Command::new("true");
+    let (generation, _) = app.app_state.tasks.track(command_task_key(&cmd), Task::<()>::none());
+    let _ = app.update(Message::App(AppMessage::SetBusy(Some("Running".to_owned()))));
+    let _ = app.update(Message::App(AppMessage::SpinnerTick));
+    assert_eq!(app.app_state.busy_frame, 1);
+
+    let output = CommandOutput { code: Some(0), stdout: String::new(), stderr: String::new() };
+    let task = app.update(Message::System(SystemMessage::ExecuteFinished(
+        cmd,
+        generation,
+        Ok(output),
+    )));
+    for action in actions(task) {
+        if let Action::Output(message) = action {
+            let _ = app.update(message);
+        }
+    }
+
+    assert_eq!(app.app_state.busy, None);
+    assert_eq!(app.app_state.busy_frame, 0);
+}
+
+#[test]
+fn cancelled_command_result_is_ignored() {
+    let mut app = test_app(true);
+    let cmd = Command::new("sleep").arg("10");
+    let (generation, _) = app.app_state.tasks.track(command_task_key(&cmd), Task::<()>::none());
+    app.app_state.busy_cancel = Some(Message::System(SystemMessage::CancelCommand(cmd.clone())));
+    let _ = app.update(Message::App(AppMessage::SetBusy(Some("Running".to_owned()))));
+
+    let task = app.update(Message::System(SystemMessage::CancelCommand(cmd.clone())));
+    for action in actions(task) {
+        if let Action::Output(message) = action {
+            let _ = app.update(message);
+        }
+    }
+    assert_eq!(app.app_state.busy, None);
+    assert!(app.app_state.busy_cancel.is_none());
+
+    let _ = app.update(Message::App(AppMessage::SetBusy(Some("Other work".to_owned()))));
+    let output = CommandOutput { code: Some(0), stdout: String::new(), stderr: String::new() };
+    let task = app.update(Message::System(SystemMessage::ExecuteFinished(
+        cmd,
+        generation,
+        Ok(output),
+    )));
+
+    assert!(actions(task).is_empty());
+    assert_eq!(app.app_state.busy.as_deref(), Some("Other work"));
+}
+
+#[test]
+fn theme_is_dark_follows_the_window_theme() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let main_id = app.app_state.main_window_id.unwrap();
+
+    app.persistent_state.current_theme = Theme::Light.to_string();
+    assert!(!app.theme_is_dark(main_id));
+
+    app.persistent_state.current_theme = Theme::Dracula.to_string();
+    assert!(app.theme_is_dark(main_id));
+}
+
+#[test]
+fn window_theme_override_applies_to_one_window() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let main_id = app.app_state.main_window_id.unwrap();
+    let other_id = window::Id::unique();
+    app.app_state.windows.insert(other_id, Window::Main);
+    app.app_state.window_order.push(other_id);
+
+    let dracula = Theme::Dracula.to_string();
+    let _ = app.update(Message::App(AppMessage::SetWindowTheme(main_id, Some(dracula))));
+    assert_eq!(app.theme(main_id), Theme::Dracula);
+    assert_eq!(app.theme(other_id), Theme::Dark);
+
+    let unknown = "Missing".to_owned();
+    let _ = app.update(Message::App(AppMessage::SetWindowTheme(main_id, Some(unknown))));
+    assert_eq!(app.theme(main_id), Theme::Dracula);
+
+    let _ = app.update(Message::App(AppMessage::SetWindowTheme(main_id, None)));
+    assert_eq!(app.theme(main_id), Theme::Dark);
+}
+
+#[test]
+fn hiding_a_window_drops_its_theme_override() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let other_id = window::Id::unique();
+    app.app_state.windows.insert(other_id, Window::Main);
+    app.app_state.window_order.push(other_id);
+
+    let light = Theme::Light.to_string();
+    let _ = app.update(Message::App(AppMessage::SetWindowTheme(other_id, Some(light))));
+    let _ = app.update(Message::App(AppMessage::Hide(other_id)));
+
+    assert!(app.app_state.window_themes.is_empty());
+}
+
+fn key_press(key: keyboard::Key, modifiers: keyboard::Modifiers) -> event::Event {
+    event::Event::Keyboard(keyboard::Event::KeyPressed {
+        modified_key: key.clone(),
+        key,
+        physical_key: keyboard::key::Physical::Unidentified(
+            keyboard::key::NativeCode::Unidentified,
+        ),
+        location: keyboard::Location::Standard,
+        modifiers,
+        text: None,
+        repeat: false,
+    })
+}
+
+#[test]
+fn command_q_exits_and_escape_dismisses() {
+    let id = window::Id::unique();
+    let q = keyboard::Key::Character("q".into());
+    let escape = keyboard::Key::Named(keyboard::key::Named::Escape);
+    let none = keyboard::Modifiers::empty();
+
+    assert!(matches!(
+        shortcut(key_press(q.clone(), keyboard::Modifiers::COMMAND), event::Status::Ignored, id),
+        Some(Message::System(SystemMessage::Exit))
+    ));
+    assert!(shortcut(key_press(q, none), event::Status::Ignored, id).is_none());
+    assert!(matches!(
+        shortcut(key_press(escape.clone(), none), event::Status::Ignored, id),
+        Some(Message::App(AppMessage::Dismiss(target))) if target == id
+    ));
+    assert!(shortcut(key_press(escape, none), event::Status::Captured, id).is_none());
+}
+
+#[test]
+fn exit_closes_the_runtime_with_windows_open() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let other_id = window::Id::unique();
+    app.app_state.windows.insert(other_id, Window::Main);
+    app.app_state.window_order.push(other_id);
+
+    let task = app.update(Message::System(SystemMessage::Exit));
+
+    assert!(matches!(actions(task).as_slice(), [Action::Exit]));
+    assert_eq!(app.app_state.windows.len(), 2);
+}
+
+#[test]
+fn hiding_a_secondary_window_only_closes_it() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let other_id = window::Id::unique();
+    app.app_state.windows.insert(other_id, Window::Main);
+    app.app_state.window_order.push(other_id);
+
+    let task = app.update(Message::App(AppMessage::Hide(other_id)));
+
+    assert!(matches!(
+        actions(task).as_slice(),
+        [Action::Window(WindowAction::Close(id))] if *id == other_id
+    ));
+    assert_eq!(app.app_state.windows.len(), 1);
+}
+
+#[test]
+fn catch_feature_panic_returns_the_panic_message() {
+    assert_eq!(catch_feature_panic(|| 7), Ok(7));
+    assert_eq!(
+        catch_feature_panic(|| -> i32 { panic!("feature broke") }),
+        Err("feature broke".to_owned())
+    );
+}
+
+#[test]
+fn app_strings_come_from_the_current_locale() {
+    let mut state = test_state(true);
+    let strings = HashMap::from([("dismiss_label".to_owned(), "Закрыть".to_owned())]);
+    let russian = Locale {
+        language: "ru".to_owned(),
+        country: "RU".to_owned(),
+        strings: HashMap::from([("app".to_owned(), strings)]),
+    };
+    state.locales.insert("ru-RU".to_owned(), russian);
+    let persistent_state =
+        PersistentState { current_locale: "ru-RU".to_owned(), ..Default::default() };
+    let app = App::with_state(state, persistent_state);
+
+    assert_eq!(app.get_string("dismiss_label"), "Закрыть");
+    assert_eq!(app.get_string("missing_key"), "Unknown");
+}
+
+#[test]
+fn resolved_theme_name_matches_the_window_theme() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let main_id = app.app_state.main_window_id.unwrap();
+    assert_eq!(app.resolved_theme_name(main_id), app.persistent_state.current_theme);
+
+    let dracula = Theme::Dracula.to_string();
+    let _ = app.update(Message::App(AppMessage::SetWindowTheme(main_id, Some(dracula.clone()))));
+
+    assert_eq!(app.resolved_theme_name(main_id), dracula);
+    assert_eq!(app.theme(main_id).to_string(), dracula);
+}
+
+fn opened_position(task: Task<Message>) -> Position {
+    match actions(task).as_slice() {
+        [Action::Window(WindowAction::Open(_, settings, _))] => settings.position,
+        other => panic!("expected a single window open, got {} actions", other.len()),
+    }
+}
+
+#[test]
+fn open_centers_the_window_on_its_anchor() {
+    let mut app = test_app(true);
+    let anchor = Rectangle::new(iced::Point::new(100.0, 50.0), iced::Size::new(1000.0, 800.0));
+    let size = Window::Main.settings().size;
+
+    let task = app.update(Message::App(AppMessage::Open(Window::Main, Some(anchor))));
+
+    let expected = iced::Point::new(600.0 - size.width / 2.0, 450.0 - size.height / 2.0);
+    assert!(matches!(opened_position(task), Position::Specific(point) if point == expected));
+}
+
+#[test]
+fn open_without_an_anchor_centers_on_screen() {
+    let mut app = test_app(true);
+
+    let task = app.update(Message::App(AppMessage::Open(Window::Main, None)));
+
+    assert!(matches!(opened_position(task), Position::Centered));
+}
+
+#[test]
+fn focus_is_tracked_for_open_windows_only() {
+    let mut app = test_app(true);
+    let _ = app.update(Message::App(AppMessage::View(Window::Main)));
+    let main_id = app.app_state.main_window_id.unwrap();
+
+    let _ = app.update(Message::App(AppMessage::WindowFocused(window::Id::unique())));
+    assert_eq!(app.app_state.focused_window, None);
+
+    let _ = app.update(Message::App(AppMessage::WindowFocused(main_id)));
+    assert_eq!(app.app_state.focused_window, Some(main_id));
+
+    let _ = app.update(Message::App(AppMessage::Hide(main_id)));
+    assert_eq!(app.app_state.focused_window, None);
+}
@@ -0,0 +1,22 @@
+// Resources compiled into the binary when the `embedded` feature is enabled.
+// Disk resources are still preferred so they can be updated without recompiling.
+
+#[cfg(feature = "embedded")]
+pub fn font() -> Option<&'static [u8]> {
+    Some(include_bytes!("../../../resources/fonts/MonacoLigaturizedNerdFont-Regular.ttf"))
+}
+
+#[cfg(not(feature = "embedded"))]
+pub fn font() -> Option<&'static [u8]> {
+    None
+}
+
+#[cfg(feature = "embedded")]
+pub fn icon() -> Option<&'static [u8]> {
+    Some(include_bytes!("../../../resources/images/icon.ico"))
+}
+
+#[cfg(not(feature = "embedded"))]
+pub fn icon() -> Option<&'static [u8]> {
+    None
+}
@@ -10,6 +10,16 @@ macro_rules! register_features {
             )+
         }
 
+        impl FeatureMessage {
+            pub fn variant_name(&self) -> &'static str {
+                match self {
+                    $(
+                        FeatureMessage::$feature(_) => concat!("Feature::", stringify!($feature)),
+                    )+
+                }
+            }
+        }
+
         #[derive(Debug, Default)]
         pub struct FeaturesState {
             $(
@@ -23,10 +33,30 @@ macro_rules! register_features {
         ) -> iced::Task<$crate::app::message::Message> {
             match msg {
                 $(
-                    FeatureMessage::$feature(fmsg) => $crate::app::features::$module::update(
-                        fmsg,
-                        $crate::app::features::$module::ContextMut::new(app)
-                    ),
+                    FeatureMessage::$feature(fmsg) => {
+                        let result = $crate::app::catch_feature_panic(|| {
+                            $crate::app::features::$module::update(
+                                fmsg,
+                                $crate::app::features::$module::ContextMut::new(app)
+                            )
+                        });
+
+                        match result {
+                            Ok(task) => task,
+                            Err(error) => {
+                                tracing::error!(
+                                    "Feature {} failed to handle a message: {}",
+                                    stringify!($feature),
+                                    error
+                                );
+                                app.app_state.feature_errors.insert(stringify!($feature), error.clone());
+                                $crate::app::features::$module::on_error(
+                                    $crate::app::features::$module::ContextMut::new(app),
+                                    &error
+                                )
+                            }
+                        }
+                    }
                 )+
             }
         }
@@ -44,6 +74,9 @@ macro_rules! register_windows {
         $(
             $window:ident {
                 settings: $settings:expr,
+                $(icon: $icon:expr,)?
+                $(theme: $theme:expr,)?
+                $(esc_closes: $esc_closes:expr,)?
                 view_handler: $view:path,
                 input_handler: $input:path,
                 context: $context:path
@@ -74,6 +107,30 @@ macro_rules! register_windows {
                 }
             }
 
+            pub fn icon_file(&self) -> Option<&'static str> {
+                match self {
+                    $(
+                        Window::$window => None$(.or(Some($icon)))?,
+                    )+
+                }
+            }
+
+            pub fn preferred_theme(&self) -> Option<&'static str> {
+                match self {
+                    $(
+                        Window::$window => None$(.or(Some($theme)))?,
+                    )+
+                }
+            }
+
+            pub fn esc_closes(&self) -> bool {
+                match self {
+                    $(
+                        Window::$window => false$(|| $esc_closes)?,
+                    )+
+                }
+            }
+
             pub fn input(&self, input_event: &$crate::app::message::InputEvent) -> iced::Task<$crate::app::message::Message> {
                 match self {
                     $(
@@ -89,7 +146,7 @@ macro_rules! register_windows {
             ) -> iced::Element<'a, $crate::app::message::Message> {
                 match self {
                     $(
-                        Window::$window => $view($context(app), wnd_id),
+                        Window::$window => $view($context(app, wnd_id), wnd_id),
                     )+
                 }
             }
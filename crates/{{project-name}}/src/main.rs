@@ -1,14 +1,17 @@
 mod app;
+mod embedded;
 mod macros;
 
-use std::{path::Path, sync::LazyLock};
+use std::{borrow::Cow, path::Path, sync::LazyLock, time::Duration};
 
 use {{crate_name}}_config::read_settings;
-use {{crate_name}}_utils::{cli, io::read_fonts, locale::read_available_locales, logging};
+use {{crate_name}}_utils::{
+    cli, crash::install_panic_hook, io::read_fonts, locale::read_available_locales, logging,
+};
 
 use anyhow::{Context, Result, anyhow};
 use iced::{Font, Settings, daemon, window::icon};
-use logging::setup_logger;
+use logging::{LogRotation, setup_logger};
 
 use crate::app::App;
 
@@ -16,15 +19,28 @@ static CONFIG: LazyLock<&Path> = LazyLock::new(|| Path::new("app_config.toml"));
 static LOCALES: LazyLock<&Path> = LazyLock::new(|| Path::new("resources/locales"));
 static IMAGES: LazyLock<&Path> = LazyLock::new(|| Path::new("resources/images"));
 static FONTS: LazyLock<&Path> = LazyLock::new(|| Path::new("resources/fonts"));
+static CRASHES: LazyLock<&Path> = LazyLock::new(|| Path::new("crashes"));
 
 fn main() -> Result<()> {
     let args = cli::parse();
     let default_log_file = format!("{}.log", env!("WORKSPACE_NAME"));
     let log_file = args.log_to_file.then_some(default_log_file).or(None);
-    setup_logger(args.verbosity, log_file).context("Failed to initialize logger.")?;
+    let no_persist = args.no_persist;
+    setup_logger(args.verbosity, log_file, Some(LogRotation::default()))
+        .context("Failed to initialize logger.")?;
+    install_panic_hook(*CRASHES);
 
     let config = read_settings(*CONFIG).context("Failed to read application settings.")?;
-    let fonts = read_fonts(*FONTS).context("Failed to read application fonts.")?;
+    let fonts = match read_fonts(*FONTS) {
+        Ok(fonts) if !fonts.is_empty() => fonts,
+        result => match embedded::font() {
+            Some(font) => {
+                tracing::warn!("No fonts found on disk, using embedded font");
+                vec![Cow::Borrowed(font)]
+            }
+            None => result.context("Failed to read application fonts.")?,
+        },
+    };
     let locales = read_available_locales(*LOCALES).context("Failed to load available locales")?;
 
     if locales.is_empty() {
@@ -40,18 +56,33 @@ fn main() -> Result<()> {
                 &icon_path.to_str().unwrap_or_default()
             )
         })
-        .ok();
+        .ok()
+        .or_else(|| embedded::icon().and_then(|data| icon::from_file_data(data, None).ok()));
 
+    let autosave_interval = config
+        .autosave_interval
+        .filter(|&secs| {
+            if secs == 0 {
+                tracing::warn!("Ignoring autosave interval of 0 seconds, autosave is disabled");
+            }
+            secs > 0
+        })
+        .map(Duration::from_secs);
     let default_font_name = config.default_font;
     let default_font = Font::with_name(Box::leak(default_font_name.into_boxed_str()));
     let settings = Settings { default_font, fonts, ..Default::default() };
 
-    daemon(move || App::new(icon.as_ref(), &locales), App::update, App::view)
-        .subscription(App::subscription)
-        .style(App::style)
-        .theme(App::theme)
-        .title(App::title)
-        .settings(settings)
-        .run()
-        .context("Failed to initialize application daemon.")
+    daemon(
+        move || App::new(icon.as_ref(), &locales, no_persist, autosave_interval),
+        App::update,
+        App::view,
+    )
+    .subscription(App::subscription)
+    .style(App::style)
+    .theme(App::theme)
+    .title(App::title)
+    .scale_factor(App::scale_factor)
+    .settings(settings)
+    .run()
+    .context("Failed to initialize application daemon.")
 }